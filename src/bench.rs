@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct Stats {
+  pub runs: usize,
+  pub min_ns: u64,
+  pub median_ns: u64,
+  pub p95_ns: u64,
+}
+
+impl Stats {
+  fn from_samples(samples: &mut [Duration]) -> Stats {
+    assert!(!samples.is_empty(), "need at least one sample");
+
+    samples.sort();
+
+    let nanos = |d: Duration| d.as_nanos() as u64;
+    let p95 = (samples.len() * 95).div_ceil(100).max(1) - 1;
+
+    Stats {
+      runs: samples.len(),
+      min_ns: nanos(samples[0]),
+      median_ns: nanos(samples[samples.len() / 2]),
+      p95_ns: nanos(samples[p95]),
+    }
+  }
+
+  pub fn min(&self) -> Duration {
+    Duration::from_nanos(self.min_ns)
+  }
+
+  pub fn median(&self) -> Duration {
+    Duration::from_nanos(self.median_ns)
+  }
+
+  pub fn p95(&self) -> Duration {
+    Duration::from_nanos(self.p95_ns)
+  }
+}
+
+pub fn measure<F, T>(warmup: usize, runs: usize, mut f: F) -> Stats
+where
+  F: FnMut() -> T,
+{
+  for _ in 0..warmup {
+    black_box(f());
+  }
+
+  let mut samples: Vec<_> = (0..runs.max(1))
+    .map(|_| {
+      let start = Instant::now();
+      black_box(f());
+      start.elapsed()
+    })
+    .collect();
+
+  Stats::from_samples(&mut samples)
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct Record {
+  pub day: u8,
+  pub part: u8,
+  pub stats: Stats,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Regression {
+  pub part: u8,
+  pub before: Stats,
+  pub after: Stats,
+}
+
+impl Regression {
+  pub fn slowdown(&self) -> f64 {
+    self.after.median_ns as f64 / self.before.median_ns.max(1) as f64 - 1.
+  }
+}
+
+// compares medians, as min is too noisy on a busy machine and p95 too sensitive to outliers.
+pub fn regressions(before: &[Record], after: &[Record], threshold: f64) -> Vec<Regression> {
+  after
+    .iter()
+    .filter_map(|now| {
+      let then = before
+        .iter()
+        .find(|then| then.day == now.day && then.part == now.part)?;
+
+      let regression = Regression {
+        part: now.part,
+        before: then.stats,
+        after: now.stats,
+      };
+
+      if regression.slowdown() * 100. > threshold {
+        Some(regression)
+      } else {
+        None
+      }
+    })
+    .collect()
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+  let contents = fs::read_to_string(path)?;
+
+  serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn save(path: &Path, records: &[Record]) -> io::Result<()> {
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(dir)?;
+  }
+
+  let contents = serde_json::to_string_pretty(records)
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+  fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(median_ns: u64) -> Stats {
+    Stats {
+      runs: 1,
+      min_ns: median_ns,
+      median_ns,
+      p95_ns: median_ns,
+    }
+  }
+
+  #[test]
+  fn percentiles() {
+    let mut samples: Vec<_> = (1..=100).rev().map(Duration::from_nanos).collect();
+    let stats = Stats::from_samples(&mut samples);
+
+    assert_eq!(stats.runs, 100);
+    assert_eq!(stats.min_ns, 1);
+    assert_eq!(stats.median_ns, 51);
+    assert_eq!(stats.p95_ns, 95);
+
+    let mut samples = vec![Duration::from_nanos(7)];
+    let stats = Stats::from_samples(&mut samples);
+
+    assert_eq!(stats.min_ns, 7);
+    assert_eq!(stats.median_ns, 7);
+    assert_eq!(stats.p95_ns, 7);
+  }
+
+  #[test]
+  fn flags_regressions() {
+    let before = vec![
+      Record {
+        day: 1,
+        part: 1,
+        stats: stats(100),
+      },
+      Record {
+        day: 1,
+        part: 2,
+        stats: stats(100),
+      },
+    ];
+
+    let after = vec![
+      Record {
+        day: 1,
+        part: 1,
+        stats: stats(105),
+      },
+      Record {
+        day: 1,
+        part: 2,
+        stats: stats(150),
+      },
+    ];
+
+    let res = regressions(&before, &after, 10.);
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].part, 2);
+    assert!((res[0].slowdown() - 0.5).abs() < 1e-9);
+  }
+}
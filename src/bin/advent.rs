@@ -1,47 +1,573 @@
 extern crate advent;
 
-use std::fs;
+use advent::solutions::{self, Picture, Recording, Solver};
+use advent::{bench, export, frames, inputs, report, scaffold, terminal, watch};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{fs, io, panic, thread};
 use structopt::StructOpt;
 
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Format {
+  Text,
+  Json,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Format, String> {
+    match s {
+      "text" => Ok(Format::Text),
+      "json" => Ok(Format::Json),
+      _ => Err(format!("unknown format: {}", s)),
+    }
+  }
+}
+
 #[derive(StructOpt, Debug)]
-#[structopt(name = "basic")]
+#[structopt(name = "advent")]
 struct Opt {
   #[structopt(short)]
-  day: u8,
+  day: Option<u8>,
+
+  /// Either text or json, which prints one record per part.
+  #[structopt(long, default_value = "text")]
+  format: Format,
+
+  /// Also writes the day's picture to this .png, .ppm or .svg path.
+  #[structopt(long, parse(from_os_str))]
+  image: Option<PathBuf>,
+
+  /// Pixels per cell of the written picture or recording.
+  #[structopt(long, default_value = "4")]
+  scale: u32,
+
+  /// Records the day's simulation as a .gif, or as numbered pngs in any other path.
+  #[structopt(long, parse(from_os_str))]
+  record: Option<PathBuf>,
+
+  /// Steps between recorded frames.
+  #[structopt(long, default_value = "1")]
+  every: usize,
+
+  /// Milliseconds each recorded gif frame is shown.
+  #[structopt(long, default_value = "50")]
+  delay: u16,
+
+  #[structopt(subcommand)]
+  command: Option<Command>,
 }
 
-fn not_implemented(_: &str) {
-  println!("not implemented yet!")
+#[derive(StructOpt, Debug)]
+enum Command {
+  /// Times each part of a day and compares against the last saved run.
+  Bench {
+    #[structopt(short)]
+    day: u8,
+
+    /// Untimed runs before measuring.
+    #[structopt(long, default_value = "3")]
+    warmup: usize,
+
+    /// Timed runs per part.
+    #[structopt(long, default_value = "25")]
+    runs: usize,
+
+    /// Percent slowdown of the median that counts as a regression.
+    #[structopt(long, default_value = "10")]
+    threshold: f64,
+
+    /// Where results are saved, defaults to ./bench/dayNN.json.
+    #[structopt(long, parse(from_os_str))]
+    results: Option<PathBuf>,
+  },
+  /// Re-runs a day whenever its input or examples/dayNN/*.txt change.
+  Watch {
+    #[structopt(short)]
+    day: u8,
+
+    /// Milliseconds between checks for changes.
+    #[structopt(long, default_value = "500")]
+    interval: u64,
+  },
+  /// Plays day 13's breakout game in the terminal.
+  Arcade {
+    /// Steer the paddle with the left and right arrow keys instead of the autopilot.
+    #[structopt(long)]
+    manual: bool,
+
+    /// Milliseconds between autopilot frames.
+    #[structopt(long, default_value = "10")]
+    delay: u64,
+  },
+  /// Shows how many asteroids each one on day 10's map can see.
+  Asteroids {
+    /// Writes the heatmap to this .png, .ppm or .svg path instead of printing it.
+    #[structopt(long, parse(from_os_str))]
+    heatmap: Option<PathBuf>,
+
+    /// Lists what the asteroid at x,y sees and what's hidden from it, defaults to the best one.
+    #[structopt(long, parse(try_from_str = station))]
+    station: Option<(i32, i32)>,
+  },
+  /// Manages the puzzle inputs kept in ./inputs.
+  Input(InputCommand),
+  /// Creates and registers a new day's module, tests and input file.
+  New {
+    #[structopt(short)]
+    day: u8,
+  },
 }
 
-pub fn main() {
-  use advent::solutions;
+#[derive(StructOpt, Debug)]
+enum InputCommand {
+  /// Stores a downloaded input under its canonical name and records its checksum.
+  Import {
+    #[structopt(parse(from_os_str))]
+    file: PathBuf,
 
-  let opt = Opt::from_args();
+    #[structopt(short)]
+    day: u8,
+  },
+  /// Shows which days have inputs and whether they still match their checksum.
+  List,
+}
+
+fn parts(day: u8) -> Option<[Solver; 2]> {
+  let parts: [Solver; 2] = match day {
+    1 => [solutions::day01::part_one, solutions::day01::part_two],
+    2 => [solutions::day02::part_one, solutions::day02::part_two],
+    3 => [solutions::day03::part_one, solutions::day03::part_two],
+    4 => [solutions::day04::part_one, solutions::day04::part_two],
+    5 => [solutions::day05::part_one, solutions::day05::part_two],
+    6 => [solutions::day06::part_one, solutions::day06::part_two],
+    7 => [solutions::day07::part_one, solutions::day07::part_two],
+    8 => [solutions::day08::part_one, solutions::day08::part_two],
+    9 => [solutions::day09::part_one, solutions::day09::part_two],
+    10 => [solutions::day10::part_one, solutions::day10::part_two],
+    11 => [solutions::day11::part_one, solutions::day11::part_two],
+    12 => [solutions::day12::part_one, solutions::day12::part_two],
+    13 => [solutions::day13::part_one, solutions::day13::part_two],
+    _ => return None,
+  };
+
+  Some(parts)
+}
+
+fn picture(day: u8) -> Option<Picture> {
+  match day {
+    8 => Some(solutions::day08::picture),
+    11 => Some(solutions::day11::picture),
+    _ => None,
+  }
+}
+
+fn recording(day: u8) -> Option<Recording> {
+  match day {
+    11 => Some(solutions::day11::record),
+    12 => Some(solutions::day12::record),
+    13 => Some(solutions::day13::record),
+    _ => None,
+  }
+}
+
+fn station(s: &str) -> Result<(i32, i32), String> {
+  let (x, y) = s
+    .split_once(',')
+    .ok_or_else(|| format!("expected x,y, found {}", s))?;
 
-  let day = opt.day;
+  let coordinate = |c: &str| c.trim().parse().map_err(|e| format!("{}: {}", c, e));
 
+  Ok((coordinate(x)?, coordinate(y)?))
+}
+
+fn read_input(day: u8) -> Result<String, inputs::Error> {
+  inputs::Store::new("./inputs").read(day)
+}
+
+fn print(format: Format, record: &report::Record) {
+  match format {
+    Format::Json => println!("{}", record.to_json()),
+    Format::Text => println!("part {}: {}", record.part, record.outcome()),
+  }
+}
+
+// side notes, kept off stdout while it carries json records.
+fn note(format: Format, message: &str) {
+  match format {
+    Format::Json => eprintln!("{}", message),
+    Format::Text => println!("{}", message),
+  }
+}
+
+fn records(day: u8) -> Vec<report::Record> {
+  let failed = |error: &str| {
+    (1..=2)
+      .map(|part| report::Record::failed(day, part, error))
+      .collect()
+  };
+
+  let parts = match parts(day) {
+    Some(parts) => parts,
+    None => return failed("not implemented yet!"),
+  };
+
+  let input = match read_input(day) {
+    Ok(input) => input,
+    Err(e) => return failed(&e.to_string()),
+  };
+
+  parts
+    .iter()
+    .enumerate()
+    .map(|(i, part)| report::run(day, i as u8 + 1, *part, &input))
+    .collect()
+}
+
+fn run(day: u8, format: Format) {
+  if format == Format::Text {
+    println!("day {:02}", day);
+  } else {
+    // the record's error field carries the message, keep stdout parseable.
+    panic::set_hook(Box::new(|_| {}));
+  }
+
+  for record in records(day) {
+    print(format, &record);
+  }
+}
+
+fn bench(day: u8, warmup: usize, runs: usize, threshold: f64, results: Option<PathBuf>) {
   println!("day {:02}", day);
 
-  let solve = match day {
-    1 => solutions::day01::solve,
-    2 => solutions::day02::solve,
-    3 => solutions::day03::solve,
-    4 => solutions::day04::solve,
-    5 => solutions::day05::solve,
-    6 => solutions::day06::solve,
-    7 => solutions::day07::solve,
-    8 => solutions::day08::solve,
-    9 => solutions::day09::solve,
-    10 => solutions::day10::solve,
-    11 => solutions::day11::solve,
-    12 => solutions::day12::solve,
-    13 => solutions::day13::solve,
-    _ => not_implemented,
-  };
-
-  let path = format!("./inputs/day{:02}.txt", day);
-  let input = fs::read_to_string(path).expect("file not found");
-
-  solve(&input);
+  let parts = match parts(day) {
+    Some(parts) => parts,
+    None => return println!("not implemented yet!"),
+  };
+
+  let input = match read_input(day) {
+    Ok(input) => input,
+    Err(e) => return println!("{}", e),
+  };
+
+  let records: Vec<_> = parts
+    .iter()
+    .enumerate()
+    .filter_map(|(i, &part)| {
+      // a failing part would time as a fast success, or panic before anything is saved.
+      let checked = report::run(day, i as u8 + 1, part, &input);
+      if let Some(error) = checked.error {
+        println!("part {}: not timed, {}", i + 1, error);
+        return None;
+      }
+
+      let stats = bench::measure(warmup, runs, || part(&input));
+
+      println!(
+        "part {}: min {:?}, median {:?}, p95 {:?} ({} runs)",
+        i + 1,
+        stats.min(),
+        stats.median(),
+        stats.p95(),
+        stats.runs
+      );
+
+      Some(bench::Record {
+        day,
+        part: i as u8 + 1,
+        stats,
+      })
+    })
+    .collect();
+
+  // a partial run would overwrite the failing parts' baseline.
+  if records.len() < parts.len() {
+    return println!("not saving results while a part fails");
+  }
+
+  let path = results.unwrap_or_else(|| PathBuf::from(format!("./bench/day{:02}.json", day)));
+
+  if let Ok(previous) = bench::load(&path) {
+    for regression in bench::regressions(&previous, &records, threshold) {
+      println!(
+        "regression in part {}: median {:?} -> {:?} (+{:.1}%)",
+        regression.part,
+        regression.before.median(),
+        regression.after.median(),
+        regression.slowdown() * 100.
+      );
+    }
+  }
+
+  bench::save(&path, &records).expect("able to save results");
+}
+
+fn new(day: u8) {
+  let touched = match scaffold::new_day(Path::new("."), day) {
+    Ok(touched) => touched,
+    Err(e) => return println!("unable to scaffold day {:02}: {}", day, e),
+  };
+
+  if touched.is_empty() {
+    println!("day {:02} already exists", day);
+  }
+
+  for path in touched {
+    println!("wrote {}", path);
+  }
+}
+
+fn watch(day: u8, interval: u64) {
+  let parts = match parts(day) {
+    Some(parts) => parts,
+    None => return println!("not implemented yet!"),
+  };
+
+  // errors end up in the answer column instead.
+  panic::set_hook(Box::new(|_| {}));
+
+  let store = inputs::Store::new("./inputs");
+  let mut watcher = watch::Watcher::new(store.path(day), format!("./examples/day{:02}", day));
+  let mut previous = HashMap::new();
+
+  loop {
+    if watcher.poll() {
+      println!("day {:02}", day);
+
+      for path in watcher.files() {
+        let contents = if path == watcher.input() {
+          store.read(day).map_err(|e| e.to_string())
+        } else {
+          fs::read_to_string(&path).map_err(|e| e.to_string())
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+          let part_no = i as u8 + 1;
+          let answer = match &contents {
+            Ok(contents) => report::run(day, part_no, *part, contents).outcome(),
+            Err(e) => format!("error: {}", e),
+          };
+
+          let before = previous
+            .insert((path.clone(), part_no), answer.clone())
+            .unwrap_or_else(|| "-".to_string());
+          let marker = if before == answer { "" } else { " (changed)" };
+
+          println!("{} part {}{}", path.display(), part_no, marker);
+          println!("{}", watch::side_by_side(&before, &answer));
+        }
+      }
+
+      println!();
+    }
+
+    thread::sleep(Duration::from_millis(interval));
+  }
+}
+
+fn input(command: InputCommand) {
+  let store = inputs::Store::new("./inputs");
+
+  match command {
+    InputCommand::Import { file, day } => {
+      let contents = match fs::read_to_string(&file) {
+        Ok(contents) => contents,
+        Err(e) => return println!("unable to read {}: {}", file.display(), e),
+      };
+
+      match store.import(day, &contents) {
+        Ok(path) => println!("wrote {}", path.display()),
+        Err(e) => println!("unable to import {}: {}", file.display(), e),
+      }
+    }
+    InputCommand::List => {
+      for day in 1..=25 {
+        println!("day {:02}: {}", day, store.status(day));
+      }
+    }
+  }
+}
+
+fn arcade(manual: bool, delay: u64) {
+  use solutions::day13;
+
+  let input = match read_input(13) {
+    Ok(input) => input,
+    Err(e) => return println!("{}", e),
+  };
+
+  let raw = if manual {
+    match terminal::RawMode::enable() {
+      Ok(raw) => Some(raw),
+      Err(e) => return println!("unable to read keys: {}", e),
+    }
+  } else {
+    None
+  };
+
+  let mut out = io::stdout();
+  let mut rate = terminal::FrameRate::new(30);
+  terminal::start(&mut out).expect("able to write to the terminal");
+
+  let played = day13::play(&input, |screen| {
+    let status = format!(
+      "score: {}  blocks: {}  fps: {:.0}",
+      screen.score(),
+      screen.blocks(),
+      rate.tick(Instant::now())
+    );
+    terminal::redraw(&mut out, &screen.render(), &status).expect("able to write to the terminal");
+
+    match &raw {
+      Some(raw) => raw.read_joystick(),
+      None => {
+        thread::sleep(Duration::from_millis(delay));
+        day13::autopilot(screen)
+      }
+    }
+  });
+
+  terminal::finish(&mut out).expect("able to write to the terminal");
+
+  match played {
+    Ok(screen) => println!("final score: {}", screen.score()),
+    Err(e) => println!("{}", e),
+  }
+}
+
+fn image(day: u8, path: &Path, scale: u32, format: Format) {
+  let picture = match picture(day) {
+    Some(picture) => picture,
+    None => return note(format, &format!("day {:02} has no picture", day)),
+  };
+
+  let input = match read_input(day) {
+    Ok(input) => input,
+    Err(e) => return note(format, &e.to_string()),
+  };
+
+  let picture = match picture(&input) {
+    Ok(picture) => picture,
+    Err(e) => return note(format, &e.to_string()),
+  };
+
+  match export::write(&picture, export::monochrome, path, scale) {
+    Ok(()) => note(format, &format!("wrote {}", path.display())),
+    Err(e) => note(
+      format,
+      &format!("unable to write {}: {}", path.display(), e),
+    ),
+  }
+}
+
+fn asteroids(heatmap: Option<PathBuf>, station: Option<(i32, i32)>, scale: u32) {
+  use solutions::day10::{self, Coordinate};
+
+  let map = match read_input(10).map(|input| day10::parse_input(&input)) {
+    Ok(Ok(map)) => map,
+    Ok(Err(e)) => return println!("{}", e),
+    Err(e) => return println!("{}", e),
+  };
+
+  let counts = day10::visible_counts(&map, solutions::workers());
+  let grid = day10::heatmap(&map, &counts);
+
+  match heatmap {
+    Some(path) => match export::write(&grid, day10::heat_palette(&grid), &path, scale) {
+      Ok(()) => println!("wrote {}", path.display()),
+      Err(e) => println!("unable to write {}: {}", path.display(), e),
+    },
+    None => println!("{}\n", day10::render_heatmap(&grid)),
+  }
+
+  let best = day10::best(&map, &counts).map(|(_, at)| at);
+  let station = match station.map(|(x, y)| Coordinate::new(x, y)).or(best) {
+    Some(station) if map.contains(&station) => station,
+    Some(station) => return println!("no asteroid at {},{}", station.x, station.y),
+    None => return println!("no asteroids on the map"),
+  };
+
+  let lines = day10::sightlines(station, &map);
+  let at = |p: &Coordinate| format!("{},{}", p.x, p.y);
+
+  println!(
+    "{} sees {}: {}",
+    at(&station),
+    lines.visible.len(),
+    lines.visible.iter().map(at).collect::<Vec<_>>().join(" ")
+  );
+  println!("{} hidden:", lines.blocked.len());
+  for (hidden, nearest) in &lines.blocked {
+    println!("  {} behind {}", at(hidden), at(nearest));
+  }
+}
+
+fn record(day: u8, path: &Path, every: usize, delay: u16, scale: u32, format: Format) {
+  let recording = match recording(day) {
+    Some(recording) => recording,
+    None => return note(format, &format!("day {:02} has no recording", day)),
+  };
+
+  let input = match read_input(day) {
+    Ok(input) => input,
+    Err(e) => return note(format, &e.to_string()),
+  };
+
+  let mut recorder = frames::Recorder::new(every);
+  if let Err(e) = recording(&input, &mut recorder) {
+    return note(format, &e.to_string());
+  }
+
+  let saved = if path.extension() == Some("gif".as_ref()) {
+    recorder.save_gif(path, scale, delay).map(|_| ())
+  } else {
+    recorder.save_pngs(path, scale).map(|_| ())
+  };
+
+  match saved {
+    Ok(()) => note(
+      format,
+      &format!("wrote {} frames to {}", recorder.len(), path.display()),
+    ),
+    Err(e) => note(
+      format,
+      &format!("unable to write {}: {}", path.display(), e),
+    ),
+  }
+}
+
+pub fn main() {
+  let opt = Opt::from_args();
+
+  match opt.command {
+    Some(Command::Bench {
+      day,
+      warmup,
+      runs,
+      threshold,
+      results,
+    }) => bench(day, warmup, runs, threshold, results),
+    Some(Command::Watch { day, interval }) => watch(day, interval),
+    Some(Command::Arcade { manual, delay }) => arcade(manual, delay),
+    Some(Command::Asteroids { heatmap, station }) => asteroids(heatmap, station, opt.scale),
+    Some(Command::Input(command)) => input(command),
+    Some(Command::New { day }) => new(day),
+    None => match opt.day {
+      Some(day) => {
+        run(day, opt.format);
+
+        if let Some(path) = opt.image {
+          image(day, &path, opt.scale, opt.format);
+        }
+
+        if let Some(path) = opt.record {
+          record(day, &path, opt.every, opt.delay, opt.scale, opt.format);
+        }
+      }
+      None => println!("pass a day with -d"),
+    },
+  }
 }
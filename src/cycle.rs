@@ -0,0 +1,222 @@
+use crate::math;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// the sequence runs `tail` steps before entering a loop of `period` states.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cycle {
+  pub tail: usize,
+  pub period: usize,
+}
+
+impl Cycle {
+  // the first step whose state was already seen.
+  pub fn first_repeat(&self) -> usize {
+    self.tail + self.period
+  }
+}
+
+// the first half of brent, finding the period from states fed one at a time,
+// so several sequences can be stepped together from one loop.
+#[derive(Debug, Clone)]
+pub struct Period<T> {
+  tortoise: T,
+  power: usize,
+  steps: usize,
+}
+
+impl<T: PartialEq + Clone> Period<T> {
+  pub fn new(start: T) -> Period<T> {
+    Period {
+      tortoise: start,
+      power: 1,
+      steps: 0,
+    }
+  }
+
+  // takes the state after the last one fed, the period once it closes a loop.
+  pub fn feed(&mut self, hare: &T) -> Option<usize> {
+    self.steps += 1;
+
+    if *hare == self.tortoise {
+      return Some(self.steps);
+    }
+
+    if self.power == self.steps {
+      self.tortoise = hare.clone();
+      self.power *= 2;
+      self.steps = 0;
+    }
+
+    None
+  }
+}
+
+// keeps two states around, and calls `f` fewer times than floyd.
+pub fn brent<T: PartialEq + Clone, F: FnMut(&T) -> T>(start: T, mut f: F) -> Cycle {
+  let mut search = Period::new(start.clone());
+  let mut hare = f(&start);
+
+  let period = loop {
+    if let Some(period) = search.feed(&hare) {
+      break period;
+    }
+
+    hare = f(&hare);
+  };
+
+  let mut tortoise = start.clone();
+  let mut hare = start;
+  for _ in 0..period {
+    hare = f(&hare);
+  }
+
+  let mut tail = 0;
+  while tortoise != hare {
+    tortoise = f(&tortoise);
+    hare = f(&hare);
+    tail += 1;
+  }
+
+  Cycle { tail, period }
+}
+
+pub fn floyd<T: PartialEq + Clone, F: FnMut(&T) -> T>(start: T, mut f: F) -> Cycle {
+  let mut tortoise = f(&start);
+  let mut hare = f(&tortoise);
+
+  while tortoise != hare {
+    tortoise = f(&tortoise);
+    let half = f(&hare);
+    hare = f(&half);
+  }
+
+  let mut tortoise = start;
+  let mut tail = 0;
+  while tortoise != hare {
+    tortoise = f(&tortoise);
+    hare = f(&hare);
+    tail += 1;
+  }
+
+  let mut period = 1;
+  let mut hare = f(&tortoise);
+  while tortoise != hare {
+    hare = f(&hare);
+    period += 1;
+  }
+
+  Cycle { tail, period }
+}
+
+// calls `f` once per step, but remembers every state until the loop closes.
+pub fn hashed<T: Hash + Eq + Clone, F: FnMut(&T) -> T>(start: T, mut f: F) -> Cycle {
+  let mut seen = HashMap::new();
+  let mut state = start;
+  let mut step = 0;
+
+  loop {
+    if let Some(&tail) = seen.get(&state) {
+      return Cycle {
+        tail,
+        period: step - tail,
+      };
+    }
+
+    let next = f(&state);
+    seen.insert(state, step);
+    state = next;
+    step += 1;
+  }
+}
+
+// dimensions that evolve independently loop together once every one of them is past its tail.
+// `None` when the combined period overflows.
+pub fn combine<I: IntoIterator<Item = Cycle>>(cycles: I) -> Option<Cycle> {
+  cycles
+    .into_iter()
+    .try_fold(Cycle { tail: 0, period: 1 }, |acc, c| {
+      Some(Cycle {
+        tail: acc.tail.max(c.tail),
+        period: math::lcm(acc.period, c.period)?,
+      })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 0, 1, 2, 3, 4, 5, 2, 3, ...
+  fn rho(x: &u32) -> u32 {
+    if *x < 5 {
+      x + 1
+    } else {
+      2
+    }
+  }
+
+  #[test]
+  fn finds_tail_and_period() {
+    let expected = Cycle { tail: 2, period: 4 };
+
+    assert_eq!(brent(0, rho), expected);
+    assert_eq!(floyd(0, rho), expected);
+    assert_eq!(hashed(0, rho), expected);
+    assert_eq!(expected.first_repeat(), 6);
+  }
+
+  #[test]
+  fn agree() {
+    let f = |x: &u64| (x * x + 1) % 1_000_003;
+
+    for start in &[0, 7, 42, 999_999] {
+      let cycle = hashed(*start, f);
+
+      assert_eq!(brent(*start, f), cycle);
+      assert_eq!(floyd(*start, f), cycle);
+    }
+
+    assert_eq!(brent(3, |x| *x), Cycle { tail: 0, period: 1 });
+  }
+
+  #[test]
+  fn periods_in_step() {
+    let (mut a, mut b) = (0, 0);
+    let mut searches = [Period::new(a), Period::new(b)];
+    let mut periods = [None, None];
+
+    while periods.contains(&None) {
+      a = rho(&a);
+      b = (b + 1) % 3;
+
+      for (p, (search, state)) in periods.iter_mut().zip(searches.iter_mut().zip(&[a, b])) {
+        if p.is_none() {
+          *p = search.feed(state);
+        }
+      }
+    }
+
+    assert_eq!(periods, [Some(4), Some(3)]);
+  }
+
+  #[test]
+  fn combines() {
+    let cycles = vec![Cycle { tail: 2, period: 4 }, Cycle { tail: 0, period: 6 }];
+
+    assert_eq!(
+      combine(cycles),
+      Some(Cycle {
+        tail: 2,
+        period: 12
+      })
+    );
+    assert_eq!(combine(vec![]), Some(Cycle { tail: 0, period: 1 }));
+
+    let huge = Cycle {
+      tail: 0,
+      period: usize::MAX - 1,
+    };
+    assert_eq!(combine(vec![huge, Cycle { tail: 0, period: 3 }]), None);
+  }
+}
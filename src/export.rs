@@ -0,0 +1,218 @@
+use crate::grid::{DenseGrid, Point};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+pub fn monochrome(lit: &bool) -> Rgb {
+  if *lit {
+    WHITE
+  } else {
+    BLACK
+  }
+}
+
+// 0 is dark red, brightening through red and yellow to white at 1.
+pub fn heat(fraction: f64) -> Rgb {
+  let f = fraction.clamp(0.0, 1.0) * 3.0;
+  let channel = |from: f64| ((f - from).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+  [channel(0.0).max(64), channel(1.0), channel(2.0)]
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Format {
+  Png,
+  Ppm,
+  Svg,
+}
+
+impl Format {
+  pub fn from_path(path: &Path) -> Option<Format> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+
+    match ext.as_str() {
+      "png" => Some(Format::Png),
+      "ppm" => Some(Format::Ppm),
+      "svg" => Some(Format::Svg),
+      _ => None,
+    }
+  }
+}
+
+// every cell becomes a `scale` by `scale` square.
+pub(crate) fn scaled<T, F: Fn(&T) -> Rgb>(
+  grid: &DenseGrid<T>,
+  palette: &F,
+  scale: u32,
+) -> (u32, u32, Vec<u8>) {
+  let scale = scale.max(1) as usize;
+  let (width, height) = (grid.width() * scale, grid.height() * scale);
+
+  let bytes = (0..height)
+    .flat_map(|y| (0..width).map(move |x| Point::new((x / scale) as isize, (y / scale) as isize)))
+    .flat_map(|p| palette(&grid[p]).to_vec())
+    .collect();
+
+  (width as u32, height as u32, bytes)
+}
+
+pub fn ppm<T, F: Fn(&T) -> Rgb>(grid: &DenseGrid<T>, palette: F, scale: u32) -> Vec<u8> {
+  let (width, height, bytes) = scaled(grid, &palette, scale);
+
+  let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+  out.extend(bytes);
+
+  out
+}
+
+// cells stay one unit wide and the scale goes into the size, with runs of a color merged into one rect.
+pub fn svg<T, F: Fn(&T) -> Rgb>(grid: &DenseGrid<T>, palette: F, scale: u32) -> String {
+  let scale = scale.max(1) as usize;
+  let mut out = String::new();
+
+  writeln!(
+    out,
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+    grid.width() * scale,
+    grid.height() * scale,
+    grid.width(),
+    grid.height()
+  )
+  .unwrap();
+
+  for (y, row) in grid.rows().enumerate() {
+    let mut x = 0;
+
+    while x < row.len() {
+      let color = palette(&row[x]);
+      let start = x;
+
+      while x < row.len() && palette(&row[x]) == color {
+        x += 1;
+      }
+
+      writeln!(
+        out,
+        r##"<rect x="{}" y="{}" width="{}" height="1" fill="#{:02x}{:02x}{:02x}"/>"##,
+        start,
+        y,
+        x - start,
+        color[0],
+        color[1],
+        color[2]
+      )
+      .unwrap();
+    }
+  }
+
+  out.push_str("</svg>\n");
+
+  out
+}
+
+// the format comes from the path's extension.
+pub fn write<T, F: Fn(&T) -> Rgb>(
+  grid: &DenseGrid<T>,
+  palette: F,
+  path: &Path,
+  scale: u32,
+) -> io::Result<()> {
+  let format = Format::from_path(path).ok_or_else(|| {
+    io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("{}: expected a .png, .ppm or .svg path", path.display()),
+    )
+  })?;
+
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(dir)?;
+  }
+
+  match format {
+    Format::Png => {
+      let (width, height, bytes) = scaled(grid, &palette, scale);
+      let img = image::RgbImage::from_raw(width, height, bytes).expect("buffer matches dimensions");
+
+      img.save(path).map_err(|e| io::Error::other(e.to_string()))
+    }
+    Format::Ppm => fs::write(path, ppm(grid, palette, scale)),
+    Format::Svg => fs::write(path, svg(grid, palette, scale)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn checkers() -> DenseGrid<bool> {
+    DenseGrid::from_vec(2, vec![true, false, true, true]).unwrap()
+  }
+
+  #[test]
+  fn formats() {
+    assert_eq!(
+      Format::from_path(Path::new("out/day11.png")),
+      Some(Format::Png)
+    );
+    assert_eq!(Format::from_path(Path::new("a.PPM")), Some(Format::Ppm));
+    assert_eq!(Format::from_path(Path::new("a.svg")), Some(Format::Svg));
+    assert_eq!(Format::from_path(Path::new("a.jpg")), None);
+    assert_eq!(Format::from_path(Path::new("a")), None);
+  }
+
+  #[test]
+  fn heats_up() {
+    assert_eq!(heat(0.0), [64, 0, 0]);
+    assert_eq!(heat(1.0 / 3.0), [255, 0, 0]);
+    assert_eq!(heat(2.0 / 3.0), [255, 255, 0]);
+    assert_eq!(heat(2.0), WHITE);
+  }
+
+  #[test]
+  fn scales_ppm() {
+    let out = ppm(&checkers(), monochrome, 2);
+    let header = b"P6\n4 4\n255\n";
+
+    assert_eq!(&out[..header.len()], header);
+    assert_eq!(out.len(), header.len() + 4 * 4 * 3);
+
+    let pixels = &out[header.len()..];
+    let at = |x: usize, y: usize| &pixels[(y * 4 + x) * 3..(y * 4 + x + 1) * 3];
+
+    assert_eq!(at(1, 1), &WHITE);
+    assert_eq!(at(2, 1), &BLACK);
+    assert_eq!(at(3, 3), &WHITE);
+  }
+
+  #[test]
+  fn merges_svg_runs() {
+    let out = svg(&checkers(), monochrome, 3);
+
+    assert!(out.starts_with(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="6" height="6" viewBox="0 0 2 2""#
+    ));
+    assert_eq!(out.matches("<rect").count(), 3);
+    assert!(out.contains(r##"<rect x="0" y="1" width="2" height="1" fill="#ffffff"/>"##));
+  }
+
+  #[test]
+  fn writes_png() {
+    let path = std::env::temp_dir().join(format!("advent-export-{}.png", std::process::id()));
+
+    write(&checkers(), monochrome, &path, 5).unwrap();
+
+    let img = image::open(&path).unwrap().to_rgb();
+    assert_eq!(img.dimensions(), (10, 10));
+    assert_eq!(img.get_pixel(7, 2).0, BLACK);
+    assert_eq!(img.get_pixel(7, 7).0, WHITE);
+    fs::remove_file(&path).unwrap();
+
+    assert!(write(&checkers(), monochrome, Path::new("out.gif"), 1).is_err());
+  }
+}
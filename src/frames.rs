@@ -0,0 +1,233 @@
+use crate::export::{self, Rgb, BLACK};
+use crate::grid::{Bounds, DenseGrid, Point, SparseGrid};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+// snapshots are kept sparse, unset cells are drawn black.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+  every: usize,
+  steps: usize,
+  frames: Vec<SparseGrid<Rgb>>,
+}
+
+impl Recorder {
+  // the first step is always captured, then every `every`th one after it.
+  pub fn new(every: usize) -> Self {
+    Self {
+      every: every.max(1),
+      steps: 0,
+      frames: vec![],
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.frames.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.frames.is_empty()
+  }
+
+  pub fn frames(&self) -> &[SparseGrid<Rgb>] {
+    &self.frames
+  }
+
+  fn due(&mut self) -> bool {
+    let due = self.steps.is_multiple_of(self.every);
+    self.steps += 1;
+
+    due
+  }
+
+  // counts as one step, and `points` is only walked when the step is captured.
+  pub fn points<I: IntoIterator<Item = (Point<isize>, Rgb)>>(&mut self, points: I) {
+    if self.due() {
+      self.frames.push(points.into_iter().collect());
+    }
+  }
+
+  pub fn sparse<T, F: Fn(&T) -> Rgb>(&mut self, grid: &SparseGrid<T>, palette: F) {
+    self.points(grid.iter().map(|(p, v)| (p, palette(v))));
+  }
+
+  pub fn dense<T, F: Fn(&T) -> Rgb>(&mut self, grid: &DenseGrid<T>, palette: F) {
+    self.points(grid.iter().map(|(p, v)| (p, palette(v))));
+  }
+
+  // every frame shares the bounds of all of them, so nothing moves when the picture grows.
+  fn canvas(&self) -> Option<Bounds<isize>> {
+    Bounds::of(
+      self
+        .frames
+        .iter()
+        .filter_map(|frame| frame.bounds())
+        .flat_map(|b| vec![b.min, b.max]),
+    )
+  }
+
+  fn render(&self) -> Vec<DenseGrid<Rgb>> {
+    let canvas = match self.canvas() {
+      Some(canvas) => canvas,
+      None => return vec![],
+    };
+
+    self
+      .frames
+      .iter()
+      .map(|frame| {
+        let mut grid = DenseGrid::new(canvas.width() as usize, canvas.height() as usize, BLACK);
+
+        for (p, &color) in frame.iter() {
+          grid.set(p - canvas.min, color);
+        }
+
+        grid
+      })
+      .collect()
+  }
+
+  // `delay` is per frame, gif only counts in hundredths of a second.
+  pub fn save_gif(&self, path: &Path, scale: u32, delay_ms: u16) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+
+    let frames = self.render();
+    let palette = palette(&frames);
+    let colors: Vec<u8> = palette.iter().flat_map(sorted).flatten().copied().collect();
+    let mut encoder = image::gif::Encoder::new(BufWriter::new(File::create(path)?));
+
+    for grid in &frames {
+      let (width, height, bytes) = export::scaled(grid, &|&c| c, scale);
+      let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "frames are too big for a gif");
+      let (width, height) = (
+        u16::try_from(width).map_err(|_| too_big())?,
+        u16::try_from(height).map_err(|_| too_big())?,
+      );
+
+      let mut frame = match &palette {
+        Some(palette) => {
+          let indices: Vec<u8> = bytes.chunks(3).map(|c| palette[c]).collect();
+
+          image::gif::Frame::from_palette_pixels(width, height, &indices, &colors, None)
+        }
+        None => image::gif::Frame::from_rgb_speed(width, height, &bytes, 10),
+      };
+      frame.delay = delay_ms / 10;
+
+      encoder
+        .encode(&frame)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    }
+
+    Ok(())
+  }
+
+  // writes frame0000.png, frame0001.png, ... into `dir`.
+  pub fn save_pngs(&self, dir: &Path, scale: u32) -> io::Result<Vec<PathBuf>> {
+    self
+      .render()
+      .iter()
+      .enumerate()
+      .map(|(i, grid)| {
+        let path = dir.join(format!("frame{:04}.png", i));
+        export::write(grid, |&c| c, &path, scale)?;
+
+        Ok(path)
+      })
+      .collect()
+  }
+}
+
+// an index per color when they fit in a gif palette, `None` when they have to be quantized.
+fn palette(frames: &[DenseGrid<Rgb>]) -> Option<HashMap<&[u8], u8>> {
+  let mut palette = HashMap::new();
+
+  for color in frames.iter().flat_map(|grid| grid.cells()) {
+    if !palette.contains_key(&color[..]) {
+      let index = u8::try_from(palette.len()).ok()?;
+      palette.insert(&color[..], index);
+    }
+  }
+
+  Some(palette)
+}
+
+fn sorted<'a>(palette: &HashMap<&'a [u8], u8>) -> Vec<&'a [u8]> {
+  let mut colors: Vec<_> = palette.iter().map(|(&c, &i)| (i, c)).collect();
+  colors.sort();
+
+  colors.into_iter().map(|(_, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::export::WHITE;
+
+  fn walk(recorder: &mut Recorder, steps: isize) {
+    for x in 0..steps {
+      recorder.points(vec![(Point::new(x, -x), WHITE)]);
+    }
+  }
+
+  #[test]
+  fn every_nth_step() {
+    let mut recorder = Recorder::new(3);
+    walk(&mut recorder, 7);
+
+    let firsts: Vec<_> = recorder
+      .frames()
+      .iter()
+      .map(|f| f.iter().next().unwrap().0)
+      .collect();
+
+    assert_eq!(recorder.len(), 3);
+    assert_eq!(
+      firsts,
+      vec![Point::new(0, 0), Point::new(3, -3), Point::new(6, -6)]
+    );
+  }
+
+  #[test]
+  fn shared_canvas() {
+    let mut recorder = Recorder::new(1);
+    walk(&mut recorder, 3);
+
+    let frames = recorder.render();
+
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|f| (f.width(), f.height()) == (3, 3)));
+    assert_eq!(frames[0][Point::new(0, 2)], WHITE);
+    assert_eq!(frames[2][Point::new(2, 0)], WHITE);
+    assert_eq!(frames[2][Point::new(0, 2)], BLACK);
+  }
+
+  #[test]
+  fn writes_gif_and_pngs() {
+    let dir = std::env::temp_dir().join(format!("advent-frames-{}", std::process::id()));
+    let mut recorder = Recorder::new(2);
+    walk(&mut recorder, 5);
+
+    let gif = dir.join("walk.gif");
+    recorder.save_gif(&gif, 2, 100).unwrap();
+
+    let img = image::open(&gif).unwrap().to_rgb();
+    assert_eq!(img.dimensions(), (10, 10));
+    assert_eq!(img.get_pixel(0, 9).0, WHITE);
+    assert_eq!(img.get_pixel(9, 0).0, BLACK);
+
+    let pngs = recorder.save_pngs(&dir.join("walk"), 1).unwrap();
+    assert_eq!(pngs.len(), 3);
+    assert!(pngs[2].ends_with("walk/frame0002.png"));
+
+    let last = image::open(&pngs[2]).unwrap().to_rgb();
+    assert_eq!(last.get_pixel(4, 0).0, WHITE);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
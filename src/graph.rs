@@ -0,0 +1,383 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+pub type Node = usize;
+
+// directed and weighted, with nodes interned by name in the order they're first seen.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+  names: Vec<String>,
+  ids: HashMap<String, Node>,
+  edges: Vec<Vec<(Node, u64)>>,
+}
+
+impl Graph {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+
+  pub fn intern(&mut self, name: &str) -> Node {
+    if let Some(&node) = self.ids.get(name) {
+      return node;
+    }
+
+    let node = self.names.len();
+    self.names.push(name.to_string());
+    self.ids.insert(name.to_string(), node);
+    self.edges.push(vec![]);
+
+    node
+  }
+
+  pub fn node(&self, name: &str) -> Option<Node> {
+    self.ids.get(name).copied()
+  }
+
+  pub fn name(&self, node: Node) -> &str {
+    &self.names[node]
+  }
+
+  pub fn nodes(&self) -> impl Iterator<Item = Node> {
+    0..self.len()
+  }
+
+  pub fn add_edge(&mut self, from: &str, to: &str, weight: u64) -> (Node, Node) {
+    let (from, to) = (self.intern(from), self.intern(to));
+    self.edges[from].push((to, weight));
+
+    (from, to)
+  }
+
+  // an edge each way.
+  pub fn connect(&mut self, a: &str, b: &str, weight: u64) -> (Node, Node) {
+    let (a, b) = self.add_edge(a, b, weight);
+    self.edges[b].push((a, weight));
+
+    (a, b)
+  }
+
+  pub fn neighbors(&self, node: Node) -> impl Iterator<Item = (Node, u64)> + '_ {
+    self.edges[node].iter().copied()
+  }
+
+  pub fn names<'a>(&'a self, route: &'a [Node]) -> impl Iterator<Item = &'a str> {
+    route.iter().map(move |&node| self.name(node))
+  }
+
+  // fewest edges from `from` to `to`, both ends included, ignoring weights.
+  pub fn bfs(&self, from: Node, to: Node) -> Option<Vec<Node>> {
+    let mut previous = vec![None; self.len()];
+    let mut queue = VecDeque::new();
+
+    previous[from] = Some(from);
+    queue.push_back(from);
+
+    while let Some(node) = queue.pop_front() {
+      if node == to {
+        return Some(route(&previous, to));
+      }
+
+      for (next, _) in self.neighbors(node) {
+        if previous[next].is_none() {
+          previous[next] = Some(node);
+          queue.push_back(next);
+        }
+      }
+    }
+
+    None
+  }
+
+  // the lightest route and its total weight.
+  pub fn dijkstra(&self, from: Node, to: Node) -> Option<(u64, Vec<Node>)> {
+    let mut dist = vec![u64::MAX; self.len()];
+    let mut previous = vec![None; self.len()];
+    let mut heap = BinaryHeap::new();
+
+    dist[from] = 0;
+    previous[from] = Some(from);
+    heap.push(Reverse((0, from)));
+
+    while let Some(Reverse((d, node))) = heap.pop() {
+      if node == to {
+        return Some((d, route(&previous, to)));
+      }
+
+      if d > dist[node] {
+        continue;
+      }
+
+      for (next, weight) in self.neighbors(node) {
+        let d = d + weight;
+
+        if d < dist[next] {
+          dist[next] = d;
+          previous[next] = Some(node);
+          heap.push(Reverse((d, next)));
+        }
+      }
+    }
+
+    None
+  }
+
+  // every node has to be reached from `root` exactly once along the edges.
+  pub fn tree(&self, root: Node) -> Result<Tree<'_>, NotATree> {
+    let mut parent = vec![None; self.len()];
+    let mut depth = vec![0; self.len()];
+    let mut order = vec![root];
+    let mut seen = vec![false; self.len()];
+    seen[root] = true;
+
+    let mut i = 0;
+    while i < order.len() {
+      let node = order[i];
+
+      for (child, _) in self.neighbors(node) {
+        if seen[child] {
+          return Err(NotATree::Revisited {
+            from: node,
+            to: child,
+          });
+        }
+
+        seen[child] = true;
+        parent[child] = Some(node);
+        depth[child] = depth[node] + 1;
+        order.push(child);
+      }
+
+      i += 1;
+    }
+
+    if let Some(node) = self.nodes().find(|&n| !seen[n]) {
+      return Err(NotATree::Unreached(node));
+    }
+
+    // up[k][n] is the 2^k-th ancestor of n, the root is its own.
+    let mut up = vec![(0..self.len())
+      .map(|n| parent[n].unwrap_or(n))
+      .collect::<Vec<_>>()];
+
+    while 1 << up.len() < self.len() {
+      let last = up.last().unwrap();
+      up.push(last.iter().map(|&n| last[n]).collect());
+    }
+
+    Ok(Tree {
+      graph: self,
+      root,
+      parent,
+      depth,
+      up,
+    })
+  }
+}
+
+// why `tree` failed, pointing at the node or edge at fault.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NotATree {
+  // the edge `from` -> `to` reaches `to` a second time.
+  Revisited { from: Node, to: Node },
+  Unreached(Node),
+}
+
+// walks back from `to` until the node that is its own previous one.
+fn route(previous: &[Option<Node>], to: Node) -> Vec<Node> {
+  let mut route = vec![to];
+  let mut node = to;
+
+  while let Some(before) = previous[node].filter(|&before| before != node) {
+    route.push(before);
+    node = before;
+  }
+
+  route.reverse();
+  route
+}
+
+#[derive(Debug, Clone)]
+pub struct Tree<'a> {
+  graph: &'a Graph,
+  root: Node,
+  parent: Vec<Option<Node>>,
+  depth: Vec<usize>,
+  up: Vec<Vec<Node>>,
+}
+
+impl<'a> Tree<'a> {
+  pub fn root(&self) -> Node {
+    self.root
+  }
+
+  pub fn parent(&self, node: Node) -> Option<Node> {
+    self.parent[node]
+  }
+
+  pub fn depth(&self, node: Node) -> usize {
+    self.depth[node]
+  }
+
+  pub fn children(&self, node: Node) -> impl Iterator<Item = Node> + 'a {
+    self.graph.neighbors(node).map(|(child, _)| child)
+  }
+
+  // `node` and everything below it.
+  pub fn subtree(&self, node: Node) -> Vec<Node> {
+    let mut nodes = vec![node];
+    let mut i = 0;
+
+    while i < nodes.len() {
+      nodes.extend(self.children(nodes[i]));
+      i += 1;
+    }
+
+    nodes
+  }
+
+  fn ancestor(&self, mut node: Node, mut steps: usize) -> Node {
+    let mut k = 0;
+
+    while steps > 0 {
+      if steps & 1 == 1 {
+        node = self.up[k][node];
+      }
+
+      steps >>= 1;
+      k += 1;
+    }
+
+    node
+  }
+
+  pub fn lca(&self, a: Node, b: Node) -> Node {
+    let (a, b) = if self.depth[a] < self.depth[b] {
+      (b, a)
+    } else {
+      (a, b)
+    };
+
+    let mut a = self.ancestor(a, self.depth[a] - self.depth[b]);
+    let mut b = b;
+
+    if a == b {
+      return a;
+    }
+
+    for level in self.up.iter().rev() {
+      if level[a] != level[b] {
+        a = level[a];
+        b = level[b];
+      }
+    }
+
+    self.up[0][a]
+  }
+
+  // edges between `a` and `b` through their common ancestor.
+  pub fn distance(&self, a: Node, b: Node) -> usize {
+    self.depth[a] + self.depth[b] - 2 * self.depth[self.lca(a, b)]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn tree() -> Graph {
+    let mut graph = Graph::new();
+
+    for (a, b) in &[
+      ("r", "a"),
+      ("r", "b"),
+      ("a", "c"),
+      ("a", "d"),
+      ("d", "e"),
+      ("b", "f"),
+    ] {
+      graph.add_edge(a, b, 1);
+    }
+
+    graph
+  }
+
+  #[test]
+  fn interns() {
+    let mut graph = tree();
+
+    assert_eq!(graph.len(), 7);
+    assert_eq!(graph.node("d"), Some(4));
+    assert_eq!(graph.intern("d"), 4);
+    assert_eq!(graph.name(6), "f");
+    assert_eq!(graph.node("x"), None);
+  }
+
+  #[test]
+  fn shortest_routes() {
+    let mut graph = Graph::new();
+    graph.connect("a", "b", 1);
+    graph.connect("b", "d", 10);
+    graph.connect("a", "c", 2);
+    graph.connect("c", "e", 2);
+    graph.connect("e", "d", 2);
+    graph.intern("island");
+
+    let (a, d) = (graph.node("a").unwrap(), graph.node("d").unwrap());
+
+    let hops = graph.bfs(a, d).unwrap();
+    assert_eq!(graph.names(&hops).collect::<Vec<_>>(), vec!["a", "b", "d"]);
+
+    let (weight, route) = graph.dijkstra(a, d).unwrap();
+    assert_eq!(weight, 6);
+    assert_eq!(
+      graph.names(&route).collect::<Vec<_>>(),
+      vec!["a", "c", "e", "d"]
+    );
+
+    assert_eq!(graph.bfs(a, a), Some(vec![a]));
+    assert_eq!(graph.bfs(a, graph.node("island").unwrap()), None);
+    assert_eq!(graph.dijkstra(d, graph.node("island").unwrap()), None);
+  }
+
+  #[test]
+  fn tree_queries() {
+    let graph = tree();
+    let tree = graph.tree(graph.node("r").unwrap()).unwrap();
+    let n = |name| graph.node(name).unwrap();
+
+    assert_eq!(tree.depth(n("e")), 3);
+    assert_eq!(tree.parent(n("e")), Some(n("d")));
+    assert_eq!(tree.parent(n("r")), None);
+    assert_eq!(
+      graph.names(&tree.subtree(n("a"))).collect::<Vec<_>>(),
+      vec!["a", "c", "d", "e"]
+    );
+
+    assert_eq!(tree.lca(n("c"), n("e")), n("a"));
+    assert_eq!(tree.lca(n("e"), n("f")), n("r"));
+    assert_eq!(tree.lca(n("d"), n("e")), n("d"));
+    assert_eq!(tree.distance(n("c"), n("f")), 4);
+  }
+
+  #[test]
+  fn not_trees() {
+    let mut graph = tree();
+    let (f, c) = graph.add_edge("f", "c", 1);
+    assert_eq!(
+      graph.tree(0).unwrap_err(),
+      NotATree::Revisited { from: f, to: c }
+    );
+
+    let mut graph = tree();
+    let loose = graph.intern("loose");
+    assert_eq!(graph.tree(0).unwrap_err(), NotATree::Unreached(loose));
+  }
+}
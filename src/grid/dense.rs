@@ -0,0 +1,252 @@
+use super::point::Point;
+use super::sparse::SparseGrid;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// row-major, with (0, 0) the top left cell and y growing down the rows.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DenseGrid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+  pub fn new(width: usize, height: usize, fill: T) -> Self {
+    Self {
+      width,
+      height,
+      cells: vec![fill; width * height],
+    }
+  }
+}
+
+impl<T> DenseGrid<T> {
+  // `None` unless the cells fill whole rows.
+  pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+    if width == 0 || !cells.len().is_multiple_of(width) {
+      return None;
+    }
+
+    Some(Self {
+      width,
+      height: cells.len() / width,
+      cells,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  pub fn into_cells(self) -> Vec<T> {
+    self.cells
+  }
+
+  fn offset(&self, p: Point<isize>) -> Option<usize> {
+    if p.x < 0 || p.y < 0 || p.x as usize >= self.width || p.y as usize >= self.height {
+      None
+    } else {
+      Some(p.y as usize * self.width + p.x as usize)
+    }
+  }
+
+  pub fn contains(&self, p: Point<isize>) -> bool {
+    self.offset(p).is_some()
+  }
+
+  pub fn get(&self, p: Point<isize>) -> Option<&T> {
+    self.offset(p).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, p: Point<isize>) -> Option<&mut T> {
+    let i = self.offset(p)?;
+    Some(&mut self.cells[i])
+  }
+
+  // returns false when `p` is outside the grid.
+  pub fn set(&mut self, p: Point<isize>, value: T) -> bool {
+    match self.get_mut(p) {
+      Some(cell) => {
+        *cell = value;
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn points(&self) -> impl Iterator<Item = Point<isize>> {
+    let width = self.width;
+    (0..self.cells.len()).map(move |i| Point::new((i % width) as isize, (i / width) as isize))
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (Point<isize>, &T)> {
+    self.points().zip(self.cells.iter())
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+  }
+
+  pub fn neighbors(&self, p: Point<isize>) -> impl Iterator<Item = (Point<isize>, &T)> {
+    p.neighbors().filter_map(move |n| Some((n, self.get(n)?)))
+  }
+
+  pub fn neighbors8(&self, p: Point<isize>) -> impl Iterator<Item = (Point<isize>, &T)> {
+    p.neighbors8().filter_map(move |n| Some((n, self.get(n)?)))
+  }
+
+  pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> DenseGrid<U> {
+    DenseGrid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  pub fn flip_vertical(&mut self) {
+    let width = self.width;
+
+    for y in 0..self.height / 2 {
+      let (top, bottom) = self.cells.split_at_mut((self.height - 1 - y) * width);
+      top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+    }
+  }
+
+  pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+    self
+      .rows()
+      .map(|row| row.iter().map(&f).collect::<String>())
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+impl<T: Clone> DenseGrid<T> {
+  // keeps only the cells `keep` accepts, at the same coordinates.
+  pub fn to_sparse<F: Fn(&T) -> bool>(&self, keep: F) -> SparseGrid<T> {
+    self
+      .iter()
+      .filter(|(_, v)| keep(v))
+      .map(|(p, v)| (p, v.clone()))
+      .collect()
+  }
+}
+
+impl<T> Index<Point<isize>> for DenseGrid<T> {
+  type Output = T;
+
+  fn index(&self, p: Point<isize>) -> &T {
+    self.get(p).expect("point inside the grid")
+  }
+}
+
+impl<T> IndexMut<Point<isize>> for DenseGrid<T> {
+  fn index_mut(&mut self, p: Point<isize>) -> &mut T {
+    self.get_mut(p).expect("point inside the grid")
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for DenseGrid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (y, row) in self.rows().enumerate() {
+      if y > 0 {
+        writeln!(f)?;
+      }
+
+      for cell in row {
+        write!(f, "{}", cell)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn grid() -> DenseGrid<u8> {
+    DenseGrid::from_vec(3, vec![1, 2, 3, 4, 5, 6]).unwrap()
+  }
+
+  #[test]
+  fn shape() {
+    let grid = grid();
+
+    assert_eq!(grid.width(), 3);
+    assert_eq!(grid.height(), 2);
+    assert_eq!(grid[Point::new(2, 1)], 6);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(DenseGrid::from_vec(4, vec![1, 2, 3, 4, 5, 6]), None);
+  }
+
+  #[test]
+  fn rows_and_columns() {
+    let grid = grid();
+
+    assert_eq!(
+      grid.rows().collect::<Vec<_>>(),
+      vec![&[1, 2, 3][..], &[4, 5, 6][..]]
+    );
+    assert_eq!(
+      grid
+        .columns()
+        .map(|c| c.copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>(),
+      vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+    );
+  }
+
+  #[test]
+  fn neighbors() {
+    let grid = grid();
+
+    let mut n: Vec<_> = grid.neighbors(Point::new(0, 0)).map(|(_, &v)| v).collect();
+    n.sort();
+    assert_eq!(n, vec![2, 4]);
+
+    let mut n: Vec<_> = grid.neighbors8(Point::new(1, 0)).map(|(_, &v)| v).collect();
+    n.sort();
+    assert_eq!(n, vec![1, 3, 4, 5, 6]);
+  }
+
+  #[test]
+  fn flip_and_render() {
+    let mut grid = DenseGrid::from_vec(2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    grid.flip_vertical();
+
+    assert_eq!(grid.cells(), &[5, 6, 3, 4, 1, 2]);
+    assert_eq!(grid.to_string(), "56\n34\n12");
+    assert_eq!(
+      grid.render(|&v| if v % 2 == 0 { '#' } else { '.' }),
+      ".#\n.#\n.#"
+    );
+  }
+
+  #[test]
+  fn to_sparse() {
+    let mut grid = grid();
+    grid.set(Point::new(1, 1), 0);
+
+    let sparse = grid.to_sparse(|&v| v != 0);
+
+    assert_eq!(sparse.len(), 5);
+    assert_eq!(sparse.get(Point::new(1, 1)), None);
+    assert_eq!(sparse.to_dense(0), grid);
+  }
+}
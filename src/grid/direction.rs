@@ -0,0 +1,186 @@
+use super::point::{Point, Scalar};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Turn {
+  Left,
+  Right,
+}
+
+// y grows upwards, so `Up` is +y.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+impl Direction {
+  pub const ALL: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+  ];
+
+  fn rotate(self, quarters: usize) -> Direction {
+    Self::ALL[(self as usize + quarters) % 4]
+  }
+
+  pub fn turn_left(self) -> Direction {
+    self.rotate(3)
+  }
+
+  pub fn turn_right(self) -> Direction {
+    self.rotate(1)
+  }
+
+  pub fn reverse(self) -> Direction {
+    self.rotate(2)
+  }
+
+  pub fn turn(self, turn: Turn) -> Direction {
+    match turn {
+      Turn::Left => self.turn_left(),
+      Turn::Right => self.turn_right(),
+    }
+  }
+
+  pub fn delta<T: Scalar>(self) -> Point<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+
+    match self {
+      Direction::Up => Point::new(zero, one),
+      Direction::Right => Point::new(one, zero),
+      Direction::Down => Point::new(zero, -one),
+      Direction::Left => Point::new(-one, zero),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction8 {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction8 {
+  pub const ALL: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+  ];
+
+  fn rotate(self, eighths: usize) -> Direction8 {
+    Self::ALL[(self as usize + eighths) % 8]
+  }
+
+  // turns are by 45 degrees.
+  pub fn turn_left(self) -> Direction8 {
+    self.rotate(7)
+  }
+
+  pub fn turn_right(self) -> Direction8 {
+    self.rotate(1)
+  }
+
+  pub fn reverse(self) -> Direction8 {
+    self.rotate(4)
+  }
+
+  pub fn turn(self, turn: Turn) -> Direction8 {
+    match turn {
+      Turn::Left => self.turn_left(),
+      Turn::Right => self.turn_right(),
+    }
+  }
+
+  pub fn delta<T: Scalar>(self) -> Point<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+
+    match self {
+      Direction8::North => Point::new(zero, one),
+      Direction8::NorthEast => Point::new(one, one),
+      Direction8::East => Point::new(one, zero),
+      Direction8::SouthEast => Point::new(one, -one),
+      Direction8::South => Point::new(zero, -one),
+      Direction8::SouthWest => Point::new(-one, -one),
+      Direction8::West => Point::new(-one, zero),
+      Direction8::NorthWest => Point::new(-one, one),
+    }
+  }
+}
+
+impl From<Direction> for Direction8 {
+  fn from(d: Direction) -> Direction8 {
+    match d {
+      Direction::Up => Direction8::North,
+      Direction::Right => Direction8::East,
+      Direction::Down => Direction8::South,
+      Direction::Left => Direction8::West,
+    }
+  }
+}
+
+impl<T: Scalar> Point<T> {
+  pub fn step(self, dir: Direction) -> Self {
+    self + dir.delta()
+  }
+
+  pub fn neighbors(self) -> impl Iterator<Item = Point<T>> {
+    Direction::ALL.iter().map(move |d| self + d.delta())
+  }
+
+  pub fn neighbors8(self) -> impl Iterator<Item = Point<T>> {
+    Direction8::ALL.iter().map(move |d| self + d.delta())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn turns() {
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Left.turn(Turn::Left), Direction::Down);
+    assert_eq!(Direction::Down.reverse(), Direction::Up);
+
+    for &d in Direction::ALL.iter() {
+      assert_eq!(d.turn_left().turn_right(), d);
+      assert_eq!(d.delta::<i32>() + d.reverse().delta(), Point::origin());
+    }
+
+    assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+    assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+    assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+
+    for &d in Direction8::ALL.iter() {
+      assert_eq!(d.delta::<i32>() + d.reverse().delta(), Point::origin());
+    }
+  }
+
+  #[test]
+  fn steps() {
+    let p = Point::new(0isize, 0);
+
+    assert_eq!(
+      p.step(Direction::Up).step(Direction::Right),
+      Point::new(1, 1)
+    );
+    assert_eq!(p.neighbors().count(), 4);
+    assert!(p.neighbors8().all(|n| n.chebyshev(p) == 1));
+  }
+}
@@ -0,0 +1,9 @@
+mod dense;
+mod direction;
+mod point;
+mod sparse;
+
+pub use dense::DenseGrid;
+pub use direction::{Direction, Direction8, Turn};
+pub use point::{Bounds, Point, Scalar};
+pub use sparse::SparseGrid;
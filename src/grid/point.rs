@@ -0,0 +1,204 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+pub trait Scalar:
+  Copy
+  + Ord
+  + Default
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Neg<Output = Self>
+{
+  const ZERO: Self;
+  const ONE: Self;
+
+  fn abs(self) -> Self;
+}
+
+macro_rules! scalar {
+  ($($t:ty),*) => {
+    $(
+      impl Scalar for $t {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn abs(self) -> Self {
+          <$t>::abs(self)
+        }
+      }
+    )*
+  };
+}
+
+scalar!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone, Default)]
+pub struct Point<T> {
+  pub x: T,
+  pub y: T,
+}
+
+impl<T> Point<T> {
+  pub const fn new(x: T, y: T) -> Self {
+    Self { x, y }
+  }
+}
+
+impl<T: Scalar> Point<T> {
+  pub fn origin() -> Self {
+    Self::new(T::ZERO, T::ZERO)
+  }
+
+  pub fn manhattan(self, other: Self) -> T {
+    let d = self - other;
+    d.x.abs() + d.y.abs()
+  }
+
+  pub fn chebyshev(self, other: Self) -> T {
+    let d = self - other;
+    d.x.abs().max(d.y.abs())
+  }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+  fn from((x, y): (T, T)) -> Self {
+    Self { x, y }
+  }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+  fn from(p: Point<T>) -> (T, T) {
+    (p.x, p.y)
+  }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Self::new(self.x + other.x, self.y + other.y)
+  }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Self::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Self::new(-self.x, -self.y)
+  }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+  type Output = Self;
+
+  fn mul(self, k: T) -> Self {
+    Self::new(self.x * k, self.y * k)
+  }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+  fn add_assign(&mut self, other: Self) {
+    self.x += other.x;
+    self.y += other.y;
+  }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+  fn sub_assign(&mut self, other: Self) {
+    self.x -= other.x;
+    self.y -= other.y;
+  }
+}
+
+// inclusive on both corners.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Bounds<T> {
+  pub min: Point<T>,
+  pub max: Point<T>,
+}
+
+impl<T: Scalar> Bounds<T> {
+  pub fn new(p: Point<T>) -> Self {
+    Self { min: p, max: p }
+  }
+
+  pub fn of<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<Self> {
+    let mut points = points.into_iter();
+    let first = points.next()?;
+
+    Some(points.fold(Self::new(first), |acc, p| acc.extend(p)))
+  }
+
+  pub fn extend(self, p: Point<T>) -> Self {
+    Self {
+      min: Point::new(self.min.x.min(p.x), self.min.y.min(p.y)),
+      max: Point::new(self.max.x.max(p.x), self.max.y.max(p.y)),
+    }
+  }
+
+  pub fn contains(&self, p: Point<T>) -> bool {
+    self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+  }
+
+  pub fn width(&self) -> T {
+    self.max.x - self.min.x + T::ONE
+  }
+
+  pub fn height(&self) -> T {
+    self.max.y - self.min.y + T::ONE
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arithmetic() {
+    let a = Point::new(1, 2);
+    let b = Point::new(-3, 5);
+
+    assert_eq!(a + b, Point::new(-2, 7));
+    assert_eq!(a - b, Point::new(4, -3));
+    assert_eq!(-a, Point::new(-1, -2));
+    assert_eq!(a * 3, Point::new(3, 6));
+
+    let mut c = a;
+    c += b;
+    c -= Point::new(1, 1);
+
+    assert_eq!(c, Point::new(-3, 6));
+    assert_eq!(<(i32, i32)>::from(c), (-3, 6));
+  }
+
+  #[test]
+  fn distances() {
+    let a = Point::new(1, 2);
+    let b = Point::new(-3, 5);
+
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!(Point::origin().manhattan(Point::new(3, -4)), 7);
+  }
+
+  #[test]
+  fn bounds() {
+    let bounds = Bounds::of(vec![Point::new(2, -1), Point::new(-1, 3), Point::new(0, 0)]).unwrap();
+
+    assert_eq!(bounds.min, Point::new(-1, -1));
+    assert_eq!(bounds.max, Point::new(2, 3));
+    assert_eq!(bounds.width(), 4);
+    assert_eq!(bounds.height(), 5);
+    assert!(bounds.contains(Point::new(0, 3)));
+    assert!(!bounds.contains(Point::new(3, 0)));
+    assert_eq!(Bounds::<i32>::of(vec![]), None);
+  }
+}
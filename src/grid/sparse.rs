@@ -0,0 +1,225 @@
+use super::dense::DenseGrid;
+use super::point::{Bounds, Point};
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+
+// bounds grow with every insert and never shrink, so a removed cell still counts towards them.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+  cells: HashMap<Point<isize>, T>,
+  bounds: Option<Bounds<isize>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+  fn default() -> Self {
+    Self {
+      cells: HashMap::new(),
+      bounds: None,
+    }
+  }
+}
+
+impl<T> SparseGrid<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  pub fn bounds(&self) -> Option<Bounds<isize>> {
+    self.bounds
+  }
+
+  pub fn insert(&mut self, p: Point<isize>, value: T) -> Option<T> {
+    self.bounds = Some(match self.bounds {
+      Some(bounds) => bounds.extend(p),
+      None => Bounds::new(p),
+    });
+
+    self.cells.insert(p, value)
+  }
+
+  pub fn remove(&mut self, p: Point<isize>) -> Option<T> {
+    self.cells.remove(&p)
+  }
+
+  pub fn get(&self, p: Point<isize>) -> Option<&T> {
+    self.cells.get(&p)
+  }
+
+  pub fn get_mut(&mut self, p: Point<isize>) -> Option<&mut T> {
+    self.cells.get_mut(&p)
+  }
+
+  pub fn contains(&self, p: Point<isize>) -> bool {
+    self.cells.contains_key(&p)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (Point<isize>, &T)> {
+    self.cells.iter().map(|(&p, v)| (p, v))
+  }
+
+  pub fn values(&self) -> impl Iterator<Item = &T> {
+    self.cells.values()
+  }
+
+  pub fn neighbors(&self, p: Point<isize>) -> impl Iterator<Item = (Point<isize>, &T)> {
+    p.neighbors().filter_map(move |n| Some((n, self.get(n)?)))
+  }
+
+  pub fn neighbors8(&self, p: Point<isize>) -> impl Iterator<Item = (Point<isize>, &T)> {
+    p.neighbors8().filter_map(move |n| Some((n, self.get(n)?)))
+  }
+
+  // every row inside the bounds, top to bottom, with `None` for unset cells.
+  pub fn rows(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+    let bounds = self.bounds;
+
+    bounds
+      .into_iter()
+      .flat_map(|b| b.min.y..=b.max.y)
+      .map(move |y| {
+        let b = bounds.unwrap();
+        (b.min.x..=b.max.x)
+          .map(|x| self.get(Point::new(x, y)))
+          .collect()
+      })
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+    let bounds = self.bounds;
+
+    bounds
+      .into_iter()
+      .flat_map(|b| b.min.x..=b.max.x)
+      .map(move |x| {
+        let b = bounds.unwrap();
+        (b.min.y..=b.max.y)
+          .map(|y| self.get(Point::new(x, y)))
+          .collect()
+      })
+  }
+
+  pub fn render<F: Fn(Option<&T>) -> char>(&self, f: F) -> String {
+    self
+      .rows()
+      .map(|row| row.into_iter().map(&f).collect::<String>())
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+impl<T: Clone> SparseGrid<T> {
+  // the bounds' top left corner becomes (0, 0), with `fill` in the unset cells.
+  pub fn to_dense(&self, fill: T) -> DenseGrid<T> {
+    let bounds = match self.bounds {
+      Some(bounds) => bounds,
+      None => return DenseGrid::new(0, 0, fill),
+    };
+
+    let mut grid = DenseGrid::new(bounds.width() as usize, bounds.height() as usize, fill);
+
+    for (p, v) in self.iter() {
+      grid.set(p - bounds.min, v.clone());
+    }
+
+    grid
+  }
+}
+
+impl<T> FromIterator<(Point<isize>, T)> for SparseGrid<T> {
+  fn from_iter<I: IntoIterator<Item = (Point<isize>, T)>>(iter: I) -> Self {
+    let mut grid = SparseGrid::new();
+
+    for (p, v) in iter {
+      grid.insert(p, v);
+    }
+
+    grid
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (y, row) in self.rows().enumerate() {
+      if y > 0 {
+        writeln!(f)?;
+      }
+
+      for cell in row {
+        match cell {
+          Some(cell) => write!(f, "{}", cell)?,
+          None => write!(f, " ")?,
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn grows() {
+    let mut grid = SparseGrid::new();
+
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.rows().count(), 0);
+
+    grid.insert(Point::new(0, 0), 'a');
+    grid.insert(Point::new(-2, 1), 'b');
+    grid.insert(Point::new(1, -1), 'c');
+
+    let bounds = grid.bounds().unwrap();
+    assert_eq!(bounds.min, Point::new(-2, -1));
+    assert_eq!(bounds.max, Point::new(1, 1));
+    assert_eq!(grid.len(), 3);
+
+    assert_eq!(grid.insert(Point::new(0, 0), 'd'), Some('a'));
+    assert_eq!(grid.len(), 3);
+  }
+
+  #[test]
+  fn rows_and_render() {
+    let grid: SparseGrid<_> = vec![
+      (Point::new(0, 0), '#'),
+      (Point::new(2, 0), '#'),
+      (Point::new(1, 1), '#'),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(grid.rows().count(), 2);
+    assert_eq!(grid.columns().count(), 3);
+    assert_eq!(grid.columns().nth(1).unwrap(), vec![None, Some(&'#')]);
+    assert_eq!(grid.to_string(), "# #\n # ");
+    assert_eq!(grid.render(|c| c.map_or('.', |_| 'x')), "x.x\n.x.");
+  }
+
+  #[test]
+  fn neighbors_and_dense() {
+    let grid: SparseGrid<_> = vec![
+      (Point::new(5, 5), 1),
+      (Point::new(6, 5), 2),
+      (Point::new(6, 6), 3),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(grid.neighbors(Point::new(5, 5)).count(), 1);
+    assert_eq!(grid.neighbors8(Point::new(5, 5)).count(), 2);
+
+    let dense = grid.to_dense(0);
+    assert_eq!(dense.cells(), &[1, 2, 0, 3]);
+  }
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const MANIFEST: &str = "checksums";
+
+#[derive(Debug)]
+pub enum Error {
+  Missing(u8),
+  Modified { day: u8, expected: u64, actual: u64 },
+  Io(io::Error),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::Missing(day) => write!(f, "no input for day {:02}", day),
+      Error::Modified {
+        day,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "input for day {:02} changed since import (checksum {:016x}, expected {:016x})",
+        day, actual, expected
+      ),
+      Error::Io(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(e: io::Error) -> Error {
+    Error::Io(e)
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Status {
+  Missing,
+  Unchecked,
+  Verified,
+  Modified,
+}
+
+impl fmt::Display for Status {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Status::Missing => write!(f, "missing"),
+      Status::Unchecked => write!(f, "no checksum"),
+      Status::Verified => write!(f, "ok"),
+      Status::Modified => write!(f, "modified"),
+    }
+  }
+}
+
+// FNV-1a, stable across toolchains unlike the std hashers.
+pub fn checksum(contents: &str) -> u64 {
+  contents.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+    (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+pub fn normalize(contents: &str) -> String {
+  let contents = contents.replace("\r\n", "\n").replace('\r', "\n");
+
+  format!("{}\n", contents.trim_end())
+}
+
+pub struct Store {
+  dir: PathBuf,
+}
+
+impl Store {
+  pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  pub fn path(&self, day: u8) -> PathBuf {
+    self.dir.join(format!("day{:02}.txt", day))
+  }
+
+  fn checksums(&self) -> io::Result<BTreeMap<u8, u64>> {
+    let contents = match fs::read_to_string(self.dir.join(MANIFEST)) {
+      Ok(contents) => contents,
+      Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+      Err(e) => return Err(e),
+    };
+
+    Ok(
+      contents
+        .lines()
+        .filter_map(|line| {
+          let mut parts = line.split_whitespace();
+          let day = parts.next()?.trim_start_matches("day").parse().ok()?;
+          let sum = u64::from_str_radix(parts.next()?, 16).ok()?;
+
+          Some((day, sum))
+        })
+        .collect(),
+    )
+  }
+
+  fn save_checksums(&self, checksums: &BTreeMap<u8, u64>) -> io::Result<()> {
+    let contents: String = checksums
+      .iter()
+      .map(|(day, sum)| format!("day{:02} {:016x}\n", day, sum))
+      .collect();
+
+    fs::write(self.dir.join(MANIFEST), contents)
+  }
+
+  // `day` has to be one `list` shows, 1 to 25.
+  pub fn import(&self, day: u8, contents: &str) -> io::Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("day {}: expected 1 to 25", day),
+      ));
+    }
+
+    let contents = normalize(contents);
+    let path = self.path(day);
+
+    fs::create_dir_all(&self.dir)?;
+    fs::write(&path, &contents)?;
+
+    let mut checksums = self.checksums()?;
+    checksums.insert(day, checksum(&contents));
+    self.save_checksums(&checksums)?;
+
+    Ok(path)
+  }
+
+  // inputs placed by hand have no checksum yet and are trusted as is.
+  pub fn read(&self, day: u8) -> Result<String, Error> {
+    let contents = match fs::read_to_string(self.path(day)) {
+      Ok(contents) => contents,
+      Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Err(Error::Missing(day)),
+      Err(e) => return Err(e.into()),
+    };
+
+    if let Some(&expected) = self.checksums()?.get(&day) {
+      let actual = checksum(&contents);
+
+      if actual != expected {
+        return Err(Error::Modified {
+          day,
+          expected,
+          actual,
+        });
+      }
+    }
+
+    Ok(contents)
+  }
+
+  pub fn status(&self, day: u8) -> Status {
+    match self.read(day) {
+      Ok(_) => match self.checksums() {
+        Ok(ref checksums) if checksums.contains_key(&day) => Status::Verified,
+        _ => Status::Unchecked,
+      },
+      Err(Error::Modified { .. }) => Status::Modified,
+      Err(_) => Status::Missing,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn store(name: &str) -> Store {
+    let dir = std::env::temp_dir().join(format!("advent-inputs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    Store::new(dir)
+  }
+
+  #[test]
+  fn normalizes_line_endings() {
+    assert_eq!(normalize("1\r\n2\r\n\r\n"), "1\n2\n");
+    assert_eq!(normalize("1\n2"), "1\n2\n");
+    assert_eq!(normalize("1,2,3\n\n\n"), "1,2,3\n");
+  }
+
+  #[test]
+  fn checksum_is_fnv() {
+    assert_eq!(checksum(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(checksum("a"), 0xaf63_dc4c_8601_ec8c);
+  }
+
+  #[test]
+  fn import_and_verify() {
+    let store = store("verify");
+
+    assert_eq!(store.status(1), Status::Missing);
+
+    let path = store.import(1, "12\r\n14\r\n").unwrap();
+
+    assert_eq!(path, store.path(1));
+    assert_eq!(store.read(1).unwrap(), "12\n14\n");
+    assert_eq!(store.status(1), Status::Verified);
+
+    fs::write(store.path(1), "12\n15\n").unwrap();
+
+    assert_eq!(store.status(1), Status::Modified);
+    assert!(store.read(1).is_err());
+
+    fs::write(store.path(2), "1,2,3\n").unwrap();
+
+    assert_eq!(store.status(2), Status::Unchecked);
+    assert_eq!(store.read(2).unwrap(), "1,2,3\n");
+
+    fs::remove_dir_all(&store.dir).unwrap();
+  }
+
+  #[test]
+  fn rejects_days_outside_advent() {
+    let store = store("days");
+
+    for &day in &[0, 26, 40] {
+      let err = store.import(day, "1\n").unwrap_err();
+
+      assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+      assert_eq!(err.to_string(), format!("day {}: expected 1 to 25", day));
+      assert!(!store.path(day).exists());
+    }
+  }
+}
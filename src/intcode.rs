@@ -1,4 +1,8 @@
+use crate::parse;
 use crossbeam::crossbeam_channel::*;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct VM {
@@ -9,6 +13,21 @@ pub struct VM {
   reader: Receiver<isize>,
   pub output: Receiver<isize>,
   writer: Sender<isize>,
+  cancelled: Arc<AtomicBool>,
+}
+
+// stops a running vm before its next instruction, waking it if it's waiting on input.
+#[derive(Debug, Clone)]
+pub struct Cancel {
+  cancelled: Arc<AtomicBool>,
+  wake: Sender<isize>,
+}
+
+impl Cancel {
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Relaxed);
+    self.wake.send(0).unwrap_or_default();
+  }
 }
 
 #[derive(PartialEq, Debug, Copy, Clone)]
@@ -78,6 +97,15 @@ impl VM {
       reader,
       output,
       writer,
+      cancelled: Arc::new(AtomicBool::new(false)),
+    }
+  }
+
+  // take it after any piping, it wakes the vm through its current input.
+  pub fn canceller(&self) -> Cancel {
+    Cancel {
+      cancelled: self.cancelled.clone(),
+      wake: self.input.clone(),
     }
   }
 
@@ -147,6 +175,10 @@ impl VM {
     let mut latest_output = 0;
 
     loop {
+      if self.cancelled.load(Ordering::Relaxed) {
+        break;
+      }
+
       let op = self.op();
       let arity = op.arity();
       match op {
@@ -217,16 +249,20 @@ impl VM {
     to.input = tx.clone();
     to.reader = rx;
   }
+
+  // feeds the vm's output back into its own input.
+  pub fn loopback(&mut self) {
+    let (tx, rx) = unbounded();
+    self.writer = tx.clone();
+    self.input = tx;
+    self.reader = rx;
+  }
 }
 
-impl From<&str> for VM {
-  fn from(input: &str) -> Self {
-    use itertools::Itertools;
-    let mem = input
-      .split(',')
-      .flat_map(|c| c.trim().parse())
-      .collect_vec();
+impl TryFrom<&str> for VM {
+  type Error = parse::Error;
 
-    Self::new(&mem)
+  fn try_from(input: &str) -> Result<Self, parse::Error> {
+    Ok(Self::new(&parse::separated(input, ',')?))
   }
 }
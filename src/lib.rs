@@ -1,3 +1,20 @@
+pub mod bench;
+pub mod cycle;
+pub mod export;
+pub mod frames;
+pub mod graph;
+pub mod grid;
+pub mod inputs;
+pub mod math;
+pub mod nbody;
+pub mod ocr;
+pub mod parse;
+pub mod report;
+pub mod scaffold;
+pub mod sif;
 pub mod solutions;
+pub mod terminal;
+pub mod watch;
+pub mod wire;
 
 mod intcode;
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+pub trait Int:
+  Copy
+  + Ord
+  + fmt::Debug
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Div<Output = Self>
+  + Rem<Output = Self>
+{
+  const ZERO: Self;
+  const ONE: Self;
+
+  fn checked_mul(self, other: Self) -> Option<Self>;
+
+  fn abs(self) -> Self;
+}
+
+pub trait Signed: Int + Neg<Output = Self> {}
+
+macro_rules! unsigned {
+  ($($t:ty),*) => {
+    $(
+      impl Int for $t {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn checked_mul(self, other: Self) -> Option<Self> {
+          <$t>::checked_mul(self, other)
+        }
+
+        fn abs(self) -> Self {
+          self
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! signed {
+  ($($t:ty),*) => {
+    $(
+      impl Int for $t {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn checked_mul(self, other: Self) -> Option<Self> {
+          <$t>::checked_mul(self, other)
+        }
+
+        fn abs(self) -> Self {
+          <$t>::abs(self)
+        }
+      }
+
+      impl Signed for $t {}
+    )*
+  };
+}
+
+unsigned!(u8, u16, u32, u64, u128, usize);
+signed!(i8, i16, i32, i64, i128, isize);
+
+// never negative, and gcd(0, 0) is 0.
+pub fn gcd<T: Int>(mut a: T, mut b: T) -> T {
+  while b != T::ZERO {
+    let r = a % b;
+    a = b;
+    b = r;
+  }
+
+  a.abs()
+}
+
+// `None` when the result doesn't fit in `T`.
+pub fn lcm<T: Int>(a: T, b: T) -> Option<T> {
+  if a == T::ZERO || b == T::ZERO {
+    return Some(T::ZERO);
+  }
+
+  (a / gcd(a, b)).checked_mul(b).map(T::abs)
+}
+
+// (g, x, y) with a * x + b * y = g = gcd(a, b).
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+  let (mut r0, mut r1) = (a, b);
+  let (mut x0, mut x1) = (T::ONE, T::ZERO);
+  let (mut y0, mut y1) = (T::ZERO, T::ONE);
+
+  while r1 != T::ZERO {
+    let q = r0 / r1;
+
+    let r = r0 - q * r1;
+    r0 = r1;
+    r1 = r;
+
+    let x = x0 - q * x1;
+    x0 = x1;
+    x1 = x;
+
+    let y = y0 - q * y1;
+    y0 = y1;
+    y1 = y;
+  }
+
+  if r0 < T::ZERO {
+    (-r0, -x0, -y0)
+  } else {
+    (r0, x0, y0)
+  }
+}
+
+// in 0..m, even for negative `a`.
+pub fn modulo<T: Int>(a: T, m: T) -> T {
+  let r = a % m;
+
+  if r < T::ZERO {
+    r + m
+  } else {
+    r
+  }
+}
+
+fn add_mod<T: Int>(a: T, b: T, m: T) -> T {
+  if a >= m - b {
+    a - (m - b)
+  } else {
+    a + b
+  }
+}
+
+// falls back to doubling when a * b would overflow.
+pub fn mul_mod<T: Int>(a: T, b: T, m: T) -> T {
+  let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+
+  if let Some(product) = a.checked_mul(b) {
+    return product % m;
+  }
+
+  let two = T::ONE + T::ONE;
+  let mut product = T::ZERO;
+
+  while b > T::ZERO {
+    if b % two == T::ONE {
+      product = add_mod(product, a, m);
+    }
+
+    a = add_mod(a, a, m);
+    b = b / two;
+  }
+
+  product
+}
+
+pub fn mod_pow<T: Int>(base: T, mut exp: u64, m: T) -> T {
+  let mut base = modulo(base, m);
+  let mut result = modulo(T::ONE, m);
+
+  while exp > 0 {
+    if exp & 1 == 1 {
+      result = mul_mod(result, base, m);
+    }
+
+    base = mul_mod(base, base, m);
+    exp >>= 1;
+  }
+
+  result
+}
+
+// `None` unless a and m are coprime.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+  let (g, x, _) = extended_gcd(modulo(a, m), m);
+
+  if g == T::ONE {
+    Some(modulo(x, m))
+  } else {
+    None
+  }
+}
+
+// the smallest x >= 0 with x = r (mod m) for every (r, m), and the lcm of the moduli.
+// the moduli needn't be coprime, `None` when the congruences disagree or the lcm overflows.
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+  congruences
+    .iter()
+    .try_fold((T::ZERO, T::ONE), |(r1, m1), &(r2, m2)| {
+      let g = gcd(m1, m2);
+      let diff = modulo(r2, m2) - modulo(r1, m2);
+
+      if diff % g != T::ZERO {
+        return None;
+      }
+
+      let m = lcm(m1, m2)?;
+      let step = m2 / g;
+      let k = mul_mod(diff / g, mod_inverse(m1 / g, step)?, step);
+      let x = add_mod(modulo(r1, m1), mul_mod(k, m1, m), m);
+
+      Some((x, m))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gcd_and_lcm() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(gcd(-12, 18), 6);
+    assert_eq!(gcd(0, -7), 7);
+    assert_eq!(gcd(0u8, 0), 0);
+    assert_eq!(gcd(270usize, 192), 6);
+
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(-4, 6), Some(12));
+    assert_eq!(lcm(0, 6), Some(0));
+    assert_eq!(lcm(200u8, 3), None);
+    assert_eq!(lcm(u64::MAX / 2, 2), Some(u64::MAX - 1));
+  }
+
+  #[test]
+  fn extended() {
+    for &(a, b) in &[(240, 46), (-7, 3), (0, 5), (17, 17)] {
+      let (g, x, y) = extended_gcd(a, b);
+
+      assert_eq!(g, gcd(a, b));
+      assert_eq!(a * x + b * y, g);
+    }
+
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(-3, 11), Some(7));
+    assert_eq!(mod_inverse(4, 12), None);
+  }
+
+  #[test]
+  fn powers() {
+    assert_eq!(mod_pow(4, 13, 497), 445);
+    assert_eq!(mod_pow(-2, 3, 5), 2);
+    assert_eq!(mod_pow(7, 0, 1), 0);
+    assert_eq!(mod_pow(2u64, 64, u64::MAX), 1);
+    assert_eq!(
+      mul_mod(i64::MAX - 1, i64::MAX - 1, i64::MAX),
+      1,
+      "(-1)^2 without overflowing"
+    );
+  }
+
+  #[test]
+  fn chinese_remainders() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+    assert_eq!(
+      crt(&[(-1, 1_000_000_007i64), (0, 998_244_353)]),
+      Some((993_328_913_953_302_348, 998_244_359_987_710_471))
+    );
+  }
+}
@@ -0,0 +1,225 @@
+use crate::cycle::{self, Cycle, Period};
+use std::convert::TryFrom;
+
+// one coordinate of every body, which moves independently of every other axis.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Axis {
+  pub positions: Vec<i64>,
+  pub velocities: Vec<i64>,
+}
+
+impl Axis {
+  // every pair pulls one step closer, then everyone moves by their velocity.
+  fn step(&mut self) {
+    let n = self.positions.len();
+
+    for i in 0..n {
+      for j in i + 1..n {
+        let pull = (self.positions[j] - self.positions[i]).signum();
+        self.velocities[i] += pull;
+        self.velocities[j] -= pull;
+      }
+    }
+
+    for (p, v) in self.positions.iter_mut().zip(&self.velocities) {
+      *p += v;
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct System {
+  axes: Vec<Axis>,
+}
+
+impl System {
+  // one position per body, every body with the same number of coordinates, starting at rest.
+  pub fn new(positions: &[Vec<i64>]) -> Option<System> {
+    let still: Vec<_> = positions.iter().map(|p| vec![0; p.len()]).collect();
+
+    System::with_velocities(positions, &still)
+  }
+
+  // `None` unless every position and velocity has the same number of coordinates.
+  pub fn with_velocities(positions: &[Vec<i64>], velocities: &[Vec<i64>]) -> Option<System> {
+    let dimensions = positions.first().map_or(0, Vec::len);
+
+    if positions.len() != velocities.len()
+      || positions
+        .iter()
+        .chain(velocities)
+        .any(|p| p.len() != dimensions)
+    {
+      return None;
+    }
+
+    let axes = (0..dimensions)
+      .map(|d| Axis {
+        positions: positions.iter().map(|p| p[d]).collect(),
+        velocities: velocities.iter().map(|v| v[d]).collect(),
+      })
+      .collect();
+
+    Some(System { axes })
+  }
+
+  pub fn bodies(&self) -> usize {
+    self.axes.first().map_or(0, |axis| axis.positions.len())
+  }
+
+  pub fn dimensions(&self) -> usize {
+    self.axes.len()
+  }
+
+  pub fn axes(&self) -> &[Axis] {
+    &self.axes
+  }
+
+  pub fn position(&self, body: usize) -> Vec<i64> {
+    self.axes.iter().map(|axis| axis.positions[body]).collect()
+  }
+
+  pub fn velocity(&self, body: usize) -> Vec<i64> {
+    self.axes.iter().map(|axis| axis.velocities[body]).collect()
+  }
+
+  pub fn step(&mut self) {
+    for axis in &mut self.axes {
+      axis.step();
+    }
+  }
+
+  // potential times kinetic, each the sum of absolute coordinates.
+  pub fn body_energy(&self, body: usize) -> i64 {
+    let (potential, kinetic) = self.axes.iter().fold((0, 0), |(p, k), axis| {
+      (
+        p + axis.positions[body].abs(),
+        k + axis.velocities[body].abs(),
+      )
+    });
+
+    potential * kinetic
+  }
+
+  pub fn energy(&self) -> i64 {
+    (0..self.bodies()).map(|body| self.body_energy(body)).sum()
+  }
+
+  // the total energy after each step.
+  pub fn energies(mut self) -> impl Iterator<Item = i64> {
+    std::iter::from_fn(move || {
+      self.step();
+      Some(self.energy())
+    })
+  }
+
+  // the whole system after each step.
+  pub fn history(mut self) -> impl Iterator<Item = System> {
+    std::iter::from_fn(move || {
+      self.step();
+      Some(self.clone())
+    })
+  }
+
+  // the period of each axis, all searched in one pass that only steps the axes still going.
+  // `None` for an axis whose bodies drift off together and never come back.
+  pub fn periods(&self) -> Vec<Option<u64>> {
+    let mut axes = self.axes.clone();
+    let mut searches: Vec<_> = axes
+      .iter()
+      .map(|axis| {
+        if axis.velocities.iter().sum::<i64>() == 0 {
+          Some(Period::new(axis.clone()))
+        } else {
+          None
+        }
+      })
+      .collect();
+    let mut periods = vec![None; axes.len()];
+
+    while searches
+      .iter()
+      .zip(&periods)
+      .any(|(s, p)| s.is_some() && p.is_none())
+    {
+      for ((axis, search), period) in axes.iter_mut().zip(&mut searches).zip(&mut periods) {
+        if let (Some(search), None) = (search, &period) {
+          axis.step();
+          *period = search.feed(axis).map(|p| p as u64);
+        }
+      }
+    }
+
+    periods
+  }
+
+  // steps until the whole system repeats, `None` when it never does or that doesn't fit in a usize.
+  // stepping can be undone, so every axis loops straight back to its start without a tail.
+  pub fn period(&self) -> Option<u64> {
+    let cycles = self
+      .periods()
+      .into_iter()
+      .map(|period| {
+        let period = usize::try_from(period?).ok()?;
+        Some(Cycle { tail: 0, period })
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    cycle::combine(cycles).and_then(|c| u64::try_from(c.period).ok())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn example() -> System {
+    System::new(&[
+      vec![-1, 0, 2],
+      vec![2, -10, -7],
+      vec![4, -8, 8],
+      vec![3, 5, -1],
+    ])
+    .unwrap()
+  }
+
+  #[test]
+  fn steps() {
+    let mut system = example();
+    system.step();
+
+    assert_eq!(system.position(0), vec![2, -1, 1]);
+    assert_eq!(system.velocity(0), vec![3, -1, -1]);
+    assert_eq!(system.velocity(3), vec![-1, -3, 1]);
+
+    assert_eq!(example().energies().nth(9), Some(179));
+    assert_eq!(
+      example().history().nth(9).unwrap().position(1),
+      vec![1, -8, 0]
+    );
+  }
+
+  #[test]
+  fn periods() {
+    let system = example();
+
+    assert_eq!(system.periods(), vec![Some(18), Some(28), Some(44)]);
+    assert_eq!(system.period(), Some(2772));
+    assert_eq!(system.clone().history().nth(2771), Some(system));
+  }
+
+  #[test]
+  fn any_shape() {
+    let line = System::new(&[vec![0], vec![3]]).unwrap();
+    assert_eq!((line.bodies(), line.dimensions()), (2, 1));
+    assert_eq!(line.period(), Some(8));
+
+    let drifting = System::with_velocities(&[vec![0, 0]], &[vec![1, 0]]).unwrap();
+    assert_eq!(drifting.periods(), vec![None, Some(1)]);
+    assert_eq!(drifting.period(), None);
+    assert_eq!(drifting.history().nth(2).unwrap().position(0), vec![3, 0]);
+
+    assert_eq!(System::new(&[vec![0, 1], vec![2]]), None);
+    assert_eq!(System::with_velocities(&[vec![0]], &[]), None);
+  }
+}
@@ -0,0 +1,247 @@
+use crate::grid::DenseGrid;
+
+type Glyph = (char, &'static [&'static str]);
+
+// glyphs are stored without their blank columns, which is also how letters are cut out of a picture.
+const SMALL: &[Glyph] = &[
+  ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+  ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+  ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+  ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+  ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+  ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+  ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+  ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+  ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+  ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+  ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+  ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+  ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+  ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+  ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+  ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE: &[Glyph] = &[
+  (
+    'A',
+    &[
+      "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'B',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#",
+      "#####.",
+    ],
+  ),
+  (
+    'C',
+    &[
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#",
+      ".####.",
+    ],
+  ),
+  (
+    'E',
+    &[
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'F',
+    &[
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'G',
+    &[
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##",
+      ".###.#",
+    ],
+  ),
+  (
+    'H',
+    &[
+      "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'J',
+    &[
+      "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.",
+      ".###..",
+    ],
+  ),
+  (
+    'K',
+    &[
+      "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.",
+      "#....#",
+    ],
+  ),
+  (
+    'L',
+    &[
+      "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'N',
+    &[
+      "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##",
+      "#....#",
+    ],
+  ),
+  (
+    'P',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'R',
+    &[
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'X',
+    &[
+      "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'Z',
+    &[
+      "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+];
+
+fn font(height: usize) -> Option<&'static [Glyph]> {
+  match height {
+    6 => Some(SMALL),
+    10 => Some(LARGE),
+    _ => None,
+  }
+}
+
+fn glyph(font: &[Glyph], rows: &[String]) -> Option<char> {
+  font
+    .iter()
+    .find(|(_, pattern)| pattern.iter().eq(rows.iter()))
+    .map(|&(c, _)| c)
+}
+
+// the font is picked by the height of the lit rows; `None` when any letter isn't in it.
+pub fn read(pixels: &DenseGrid<bool>) -> Option<String> {
+  let rows: Vec<&[bool]> = pixels.rows().collect();
+
+  let top = rows.iter().position(|row| row.iter().any(|&p| p))?;
+  let bottom = rows.iter().rposition(|row| row.iter().any(|&p| p))?;
+  let rows = &rows[top..=bottom];
+
+  let font = font(rows.len())?;
+
+  let lit = |x: usize| rows.iter().any(|row| row[x]);
+  let mut letters = vec![];
+  let mut x = 0;
+
+  while x < pixels.width() {
+    if !lit(x) {
+      x += 1;
+      continue;
+    }
+
+    let start = x;
+    while x < pixels.width() && lit(x) {
+      x += 1;
+    }
+
+    let glyph_rows: Vec<String> = rows
+      .iter()
+      .map(|row| {
+        row[start..x]
+          .iter()
+          .map(|&p| if p { '#' } else { '.' })
+          .collect()
+      })
+      .collect();
+
+    letters.push(glyph(font, &glyph_rows)?);
+  }
+
+  Some(letters.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn picture(font: &[Glyph], text: &str, gap: usize) -> DenseGrid<bool> {
+    let glyphs: Vec<_> = text
+      .chars()
+      .map(|c| font.iter().find(|(g, _)| *g == c).unwrap().1)
+      .collect();
+    let height = glyphs[0].len();
+
+    let rows: Vec<String> = (0..height)
+      .map(|y| {
+        glyphs
+          .iter()
+          .map(|g| g[y])
+          .collect::<Vec<_>>()
+          .join(&".".repeat(gap))
+      })
+      .collect();
+
+    let width = rows[0].len() + 2;
+    let blank = ".".repeat(width);
+
+    let cells = std::iter::once(blank.clone())
+      .chain(rows.iter().map(|row| format!(".{}.", row)))
+      .chain(std::iter::once(blank))
+      .flat_map(|row| row.chars().map(|c| c == '#').collect::<Vec<_>>())
+      .collect();
+
+    DenseGrid::from_vec(width, cells).unwrap()
+  }
+
+  #[test]
+  fn small() {
+    assert_eq!(read(&picture(SMALL, "HELLO", 1)).unwrap(), "HELLO");
+    assert_eq!(read(&picture(SMALL, "ZIPY", 1)).unwrap(), "ZIPY");
+
+    let all: String = SMALL.iter().map(|(c, _)| c).collect();
+    assert_eq!(read(&picture(SMALL, &all, 1)).unwrap(), all);
+  }
+
+  #[test]
+  fn large() {
+    let all: String = LARGE.iter().map(|(c, _)| c).collect();
+    assert_eq!(read(&picture(LARGE, &all, 2)).unwrap(), all);
+  }
+
+  #[test]
+  fn unknown() {
+    let mut pixels = picture(SMALL, "HI", 1);
+    pixels.set(crate::grid::Point::new(2, 1), true);
+
+    assert_eq!(read(&pixels), None);
+    assert_eq!(read(&DenseGrid::new(3, 3, false)), None);
+  }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+// lines and columns count from 1, columns in chars.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Error {
+  // the line and column, `None` when the input as a whole is at fault.
+  pub position: Option<(usize, usize)>,
+  pub message: String,
+}
+
+impl Error {
+  // about the whole input, with nowhere in particular to point at.
+  pub fn new<S: Into<String>>(message: S) -> Error {
+    Error {
+      position: None,
+      message: message.into(),
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.position {
+      Some((line, column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+      None => write!(f, "{}", self.message),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+// a piece of the input that knows where it starts, so errors can point at it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Token<'a> {
+  pub text: &'a str,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl<'a> From<&'a str> for Token<'a> {
+  fn from(text: &'a str) -> Token<'a> {
+    Token {
+      text,
+      line: 1,
+      column: 1,
+    }
+  }
+}
+
+impl<'a> Token<'a> {
+  pub fn error<S: Into<String>>(&self, message: S) -> Error {
+    Error {
+      position: Some((self.line, self.column)),
+      message: message.into(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+
+  // `range` is in bytes, like slicing a str.
+  pub fn slice(&self, range: Range<usize>) -> Token<'a> {
+    let (line, column) =
+      self.text[..range.start]
+        .chars()
+        .fold((self.line, self.column), |(line, column), c| {
+          if c == '\n' {
+            (line + 1, 1)
+          } else {
+            (line, column + 1)
+          }
+        });
+
+    Token {
+      text: &self.text[range],
+      line,
+      column,
+    }
+  }
+
+  pub fn trim(&self) -> Token<'a> {
+    let start = self.text.len() - self.text.trim_start().len();
+    let end = self.text.trim_end().len().max(start);
+
+    self.slice(start..end)
+  }
+
+  // every piece is trimmed, and empty pieces are kept so they can be reported.
+  pub fn split(self, sep: char) -> impl Iterator<Item = Token<'a>> {
+    let mut rest = Some(self);
+
+    std::iter::from_fn(move || {
+      let token = rest?;
+
+      match token.text.find(sep) {
+        Some(i) => {
+          rest = Some(token.slice(i + sep.len_utf8()..token.text.len()));
+          Some(token.slice(0..i).trim())
+        }
+        None => {
+          rest = None;
+          Some(token.trim())
+        }
+      }
+    })
+  }
+
+  pub fn split_once(&self, sep: char) -> Result<(Token<'a>, Token<'a>), Error> {
+    let i = self
+      .text
+      .find(sep)
+      .ok_or_else(|| self.error(format!("expected `{}` in `{}`", sep, self.text)))?;
+
+    Ok((
+      self.slice(0..i).trim(),
+      self.slice(i + sep.len_utf8()..self.text.len()).trim(),
+    ))
+  }
+
+  // one token per char.
+  pub fn chars(self) -> impl Iterator<Item = Token<'a>> {
+    let (mut line, mut column) = (self.line, self.column);
+
+    self.text.char_indices().map(move |(i, c)| {
+      let token = Token {
+        text: &self.text[i..i + c.len_utf8()],
+        line,
+        column,
+      };
+
+      if c == '\n' {
+        line += 1;
+        column = 1;
+      } else {
+        column += 1;
+      }
+
+      token
+    })
+  }
+
+  pub fn parse<T>(&self) -> Result<T, Error>
+  where
+    T: FromStr,
+    T::Err: fmt::Display,
+  {
+    self
+      .text
+      .parse()
+      .map_err(|e| self.error(format!("`{}`: {}", self.text, e)))
+  }
+}
+
+// trimmed lines, skipping blank ones.
+pub fn lines(input: &str) -> impl Iterator<Item = Token<'_>> {
+  input
+    .lines()
+    .enumerate()
+    .map(|(i, text)| {
+      Token {
+        text,
+        line: i + 1,
+        column: 1,
+      }
+      .trim()
+    })
+    .filter(|token| !token.is_empty())
+}
+
+// values split by `sep` across the whole input, which may wrap over several lines.
+pub fn separated<T>(input: &str, sep: char) -> Result<Vec<T>, Error>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  Token::from(input).split(sep).map(|t| t.parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn positions() {
+    let lines: Vec<_> = lines("ab\n\n  cd \n").collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+      (lines[1].text, lines[1].line, lines[1].column),
+      ("cd", 3, 3)
+    );
+
+    let token = Token::from("1,\n  22 ,x");
+    let parts: Vec<_> = token.split(',').collect();
+
+    assert_eq!(
+      parts
+        .iter()
+        .map(|t| (t.text, t.line, t.column))
+        .collect::<Vec<_>>(),
+      vec![("1", 1, 1), ("22", 2, 3), ("x", 2, 7)]
+    );
+
+    let chars: Vec<_> = Token::from("é\nx").chars().collect();
+    assert_eq!((chars[2].text, chars[2].line, chars[2].column), ("x", 2, 1));
+  }
+
+  #[test]
+  fn errors() {
+    let err = separated::<u8>("1, 2,\n  3x", ',').unwrap_err();
+
+    assert_eq!(err.position, Some((2, 3)));
+    assert_eq!(
+      err.to_string(),
+      "line 2, column 3: `3x`: invalid digit found in string"
+    );
+
+    assert_eq!(separated::<u8>("1,2\n", ',').unwrap(), vec![1, 2]);
+    assert!(separated::<u8>("1,,2", ',').is_err());
+
+    assert_eq!(Error::new("no moons").to_string(), "no moons");
+
+    let line = lines("\nCOM-B").next().unwrap();
+    assert_eq!(
+      line.split_once(')').unwrap_err().to_string(),
+      "line 2, column 1: expected `)` in `COM-B`"
+    );
+  }
+}
@@ -0,0 +1,134 @@
+use crate::solutions::Solver;
+use serde::{Deserialize, Serialize};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+pub const YEAR: u16 = 2019;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Record {
+  pub year: u16,
+  pub day: u8,
+  pub part: u8,
+  pub answer: Option<String>,
+  pub duration_ms: f64,
+  pub error: Option<String>,
+}
+
+impl Record {
+  pub fn failed(day: u8, part: u8, error: &str) -> Record {
+    Record {
+      year: YEAR,
+      day,
+      part,
+      answer: None,
+      duration_ms: 0.,
+      error: Some(error.to_string()),
+    }
+  }
+
+  // the answer, or the error when there is none.
+  pub fn outcome(&self) -> String {
+    match (&self.answer, &self.error) {
+      (Some(answer), _) => answer.clone(),
+      (None, Some(error)) => format!("error: {}", error),
+      (None, None) => String::new(),
+    }
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string(self).expect("records are always serializable")
+  }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+  if let Some(s) = payload.downcast_ref::<&str>() {
+    s.to_string()
+  } else if let Some(s) = payload.downcast_ref::<String>() {
+    s.clone()
+  } else {
+    "solver panicked".to_string()
+  }
+}
+
+// a panicking solver or bad input becomes an error record, so one bad part doesn't lose the rest of the report.
+pub fn run(day: u8, part: u8, solver: Solver, input: &str) -> Record {
+  let start = Instant::now();
+  let res = panic::catch_unwind(AssertUnwindSafe(|| solver(input)));
+  let duration_ms = start.elapsed().as_secs_f64() * 1_000.;
+
+  let (answer, error) = match res {
+    Ok(Ok(answer)) => (Some(answer), None),
+    Ok(Err(e)) => (None, Some(e.to_string())),
+    Err(payload) => (None, Some(panic_message(payload))),
+  };
+
+  Record {
+    year: YEAR,
+    day,
+    part,
+    answer,
+    duration_ms,
+    error,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parse;
+
+  fn answer(input: &str) -> Result<String, parse::Error> {
+    Ok(input.len().to_string())
+  }
+
+  fn broken(_: &str) -> Result<String, parse::Error> {
+    panic!("bad input")
+  }
+
+  fn unparsable(input: &str) -> Result<String, parse::Error> {
+    Err(parse::Token::from(input).error("expected a number"))
+  }
+
+  #[test]
+  fn records_answer() {
+    let record = run(1, 2, answer, "abc");
+
+    assert_eq!(record.year, 2019);
+    assert_eq!(record.day, 1);
+    assert_eq!(record.part, 2);
+    assert_eq!(record.answer.as_deref(), Some("3"));
+    assert_eq!(record.error, None);
+  }
+
+  #[test]
+  fn records_panic() {
+    panic::set_hook(Box::new(|_| {}));
+    let record = run(1, 1, broken, "");
+    let _ = panic::take_hook();
+
+    assert_eq!(record.answer, None);
+    assert_eq!(record.error.as_deref(), Some("bad input"));
+  }
+
+  #[test]
+  fn records_parse_error() {
+    let record = run(1, 1, unparsable, "x");
+
+    assert_eq!(record.answer, None);
+    assert_eq!(
+      record.error.as_deref(),
+      Some("line 1, column 1: expected a number")
+    );
+  }
+
+  #[test]
+  fn json_fields() {
+    let json = Record::failed(3, 1, "missing input").to_json();
+
+    assert_eq!(
+      json,
+      r#"{"year":2019,"day":3,"part":1,"answer":null,"duration_ms":0.0,"error":"missing input"}"#
+    );
+  }
+}
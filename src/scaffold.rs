@@ -0,0 +1,161 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const TEMPLATE: &str = include_str!("solutions/dayN.rs");
+
+fn module(day: u8) -> String {
+  format!("day{:02}", day)
+}
+
+fn invalid(msg: String) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+// keeps `pub mod dayNN;` lines sorted, so the new day lands after the one before it.
+pub fn register_module(mod_rs: &str, day: u8) -> Option<String> {
+  let line = format!("pub mod {};", module(day));
+
+  if mod_rs.lines().any(|l| l.trim() == line) {
+    return None;
+  }
+
+  let mut lines: Vec<&str> = mod_rs.lines().collect();
+  let at = lines
+    .iter()
+    .rposition(|l| l.starts_with("pub mod day") && l.trim() < line.as_str())
+    .map(|i| i + 1)
+    .unwrap_or(0);
+
+  lines.insert(at, &line);
+
+  Some(lines.join("\n") + "\n")
+}
+
+// adds an arm to `parts` in the binary, right above its catch-all.
+pub fn register_arm(bin: &str, day: u8) -> Option<String> {
+  let catch_all = "    _ => return None,";
+  let name = module(day);
+  let arm = format!(
+    "    {} => [solutions::{}::part_one, solutions::{}::part_two],",
+    day, name, name
+  );
+
+  if bin.contains(&arm) {
+    return None;
+  }
+
+  let at = bin.find(catch_all)?;
+
+  Some(format!("{}{}\n{}", &bin[..at], arm, &bin[at..]))
+}
+
+fn write_new(path: &Path, contents: &str) -> io::Result<bool> {
+  if path.exists() {
+    return Ok(false);
+  }
+
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(dir)?;
+  }
+
+  fs::write(path, contents)?;
+
+  Ok(true)
+}
+
+fn update<F>(path: &Path, f: F) -> io::Result<bool>
+where
+  F: FnOnce(&str) -> Option<String>,
+{
+  let contents = fs::read_to_string(path)?;
+
+  match f(&contents) {
+    Some(updated) => fs::write(path, updated).map(|_| true),
+    None => Ok(false),
+  }
+}
+
+// paths relative to the crate root, returns the ones that were created or changed.
+pub fn new_day(root: &Path, day: u8) -> io::Result<Vec<String>> {
+  if !(1..=25).contains(&day) {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("day {}: expected 1 to 25", day),
+    ));
+  }
+
+  let name = module(day);
+  let solution = root.join(format!("src/solutions/{}.rs", name));
+  let mod_rs = root.join("src/solutions/mod.rs");
+  let bin = root.join("src/bin/advent.rs");
+  let input = root.join(format!("inputs/{}.txt", name));
+
+  let mut touched = vec![];
+
+  if write_new(&solution, TEMPLATE)? {
+    touched.push(solution.display().to_string());
+  }
+
+  if update(&mod_rs, |s| register_module(s, day))? {
+    touched.push(mod_rs.display().to_string());
+  }
+
+  let contents = fs::read_to_string(&bin)?;
+  if !contents.contains(&format!("solutions::{}::part_one", name)) {
+    let updated = register_arm(&contents, day)
+      .ok_or_else(|| invalid(format!("no catch-all arm in {}", bin.display())))?;
+    fs::write(&bin, updated)?;
+    touched.push(bin.display().to_string());
+  }
+
+  if write_new(&input, "")? {
+    touched.push(input.display().to_string());
+  }
+
+  Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn registers_module_in_order() {
+    let mod_rs = "pub mod day01;\npub mod day03;\n\npub type Solver = fn(&str) -> Result<String, parse::Error>;\n";
+
+    assert_eq!(
+      register_module(mod_rs, 2).unwrap(),
+      "pub mod day01;\npub mod day02;\npub mod day03;\n\npub type Solver = fn(&str) -> Result<String, parse::Error>;\n"
+    );
+    assert_eq!(
+      register_module(mod_rs, 14).unwrap(),
+      "pub mod day01;\npub mod day03;\npub mod day14;\n\npub type Solver = fn(&str) -> Result<String, parse::Error>;\n"
+    );
+    assert_eq!(register_module(mod_rs, 3), None);
+  }
+
+  #[test]
+  fn rejects_days_outside_advent() {
+    for &day in &[0, 26, 120] {
+      let err = new_day(Path::new("/nonexistent"), day).unwrap_err();
+
+      assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+      assert_eq!(err.to_string(), format!("day {}: expected 1 to 25", day));
+    }
+  }
+
+  #[test]
+  fn registers_arm() {
+    let bin = "  match day {\n    1 => [solutions::day01::part_one, solutions::day01::part_two],\n    _ => return None,\n  };\n";
+
+    let res = register_arm(bin, 14).unwrap();
+
+    assert_eq!(
+      res,
+      "  match day {\n    1 => [solutions::day01::part_one, solutions::day01::part_two],\n    14 => [solutions::day14::part_one, solutions::day14::part_two],\n    _ => return None,\n  };\n"
+    );
+    assert_eq!(register_arm(&res, 14), None);
+    assert_eq!(register_arm("fn main() {}", 14), None);
+  }
+}
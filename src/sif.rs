@@ -0,0 +1,209 @@
+use crate::grid::DenseGrid;
+use crate::parse::{self, Token};
+
+// the space image format: layers of width × height digits, the first layer on top.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SifImage {
+  width: usize,
+  height: usize,
+  layers: Vec<DenseGrid<u8>>,
+}
+
+impl SifImage {
+  pub fn parse(input: &str, width: usize, height: usize) -> Result<SifImage, parse::Error> {
+    let token = Token::from(input).trim();
+    let size = width * height;
+
+    if size == 0 {
+      return Err(token.error(format!("a {}×{} layer holds no digits", width, height)));
+    }
+
+    let digits = token
+      .chars()
+      .map(|c| {
+        c.text
+          .parse::<u8>()
+          .map_err(|_| c.error(format!("expected a digit, found `{}`", c.text)))
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    if digits.is_empty() || digits.len() % size != 0 {
+      // every digit is one byte, so the last whole layer ends at a byte offset.
+      let whole = digits.len() / size * size;
+
+      return Err(token.slice(whole..token.text.len()).error(format!(
+        "{} digits don't make whole {}×{} layers of {}",
+        digits.len(),
+        width,
+        height,
+        size
+      )));
+    }
+
+    let layers = digits
+      .chunks_exact(size)
+      .map(|layer| DenseGrid::from_vec(width, layer.to_vec()).expect("whole rows"))
+      .collect();
+
+    Ok(SifImage {
+      width,
+      height,
+      layers,
+    })
+  }
+
+  // `None` without layers, when they aren't all the same size, or hold anything but digits.
+  pub fn from_layers(layers: Vec<DenseGrid<u8>>) -> Option<SifImage> {
+    let (width, height) = layers.first().map(|l| (l.width(), l.height()))?;
+
+    if layers
+      .iter()
+      .any(|l| l.width() != width || l.height() != height || l.cells().iter().any(|&d| d > 9))
+    {
+      return None;
+    }
+
+    Some(SifImage {
+      width,
+      height,
+      layers,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn layers(&self) -> &[DenseGrid<u8>] {
+    &self.layers
+  }
+
+  // how often each digit shows up in every layer.
+  pub fn histograms(&self) -> Vec<[usize; 10]> {
+    self
+      .layers
+      .iter()
+      .map(|layer| {
+        let mut counts = [0; 10];
+        for &digit in layer.cells() {
+          counts[digit as usize] += 1;
+        }
+        counts
+      })
+      .collect()
+  }
+
+  // ones times twos in the layer with the fewest zeros, the first one on a tie.
+  pub fn checksum(&self) -> usize {
+    self
+      .histograms()
+      .into_iter()
+      .min_by_key(|counts| counts[0])
+      .map(|counts| counts[1] * counts[2])
+      .expect("at least one layer")
+  }
+
+  // each pixel takes the first digit other than `transparent`, from the top down,
+  // and stays `transparent` when every layer is.
+  pub fn composite(&self, transparent: u8) -> DenseGrid<u8> {
+    let mut image = DenseGrid::new(self.width, self.height, transparent);
+
+    for layer in self.layers.iter().rev() {
+      for (p, &digit) in layer.iter() {
+        if digit != transparent {
+          image.set(p, digit);
+        }
+      }
+    }
+
+    image
+  }
+
+  // back to the digits `parse` reads.
+  pub fn encode(&self) -> String {
+    self.layers.iter().map(encode).collect()
+  }
+}
+
+// one layer's digits, row by row.
+pub fn encode(grid: &DenseGrid<u8>) -> String {
+  grid
+    .cells()
+    .iter()
+    .map(|&digit| char::from(b'0' + digit))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn layers_and_checksum() {
+    let image = SifImage::parse("123456789012\n", 3, 2).unwrap();
+
+    assert_eq!(image.layers().len(), 2);
+    assert_eq!(image.layers()[1].cells(), &[7, 8, 9, 0, 1, 2]);
+    assert_eq!(image.histograms()[1], [1, 1, 1, 0, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(image.checksum(), 1);
+    assert_eq!(image.encode(), "123456789012");
+  }
+
+  #[test]
+  fn composites() {
+    let image = SifImage::parse("0222112222120000", 2, 2).unwrap();
+
+    assert_eq!(image.composite(2).cells(), &[0, 1, 1, 0]);
+    assert_eq!(image.composite(0).cells(), &[1, 2, 2, 2]);
+
+    let clear = SifImage::parse("22", 1, 1).unwrap();
+    assert_eq!(clear.composite(2).cells(), &[2]);
+  }
+
+  #[test]
+  fn round_trips() {
+    let layers = vec![
+      DenseGrid::from_vec(2, vec![9, 2, 2, 0]).unwrap(),
+      DenseGrid::from_vec(2, vec![1, 1, 1, 1]).unwrap(),
+    ];
+    let image = SifImage::from_layers(layers.clone()).unwrap();
+
+    assert_eq!(SifImage::parse(&image.encode(), 2, 2), Ok(image));
+    assert_eq!(encode(&layers[0]), "9220");
+
+    let uneven = vec![layers[0].clone(), DenseGrid::new(1, 4, 0)];
+    assert_eq!(SifImage::from_layers(uneven), None);
+    assert_eq!(SifImage::from_layers(vec![]), None);
+    assert_eq!(SifImage::from_layers(vec![DenseGrid::new(1, 1, 10)]), None);
+  }
+
+  #[test]
+  fn bad_images() {
+    let err = |input, width, height| {
+      SifImage::parse(input, width, height)
+        .unwrap_err()
+        .to_string()
+    };
+
+    assert_eq!(
+      err("1234567", 3, 2),
+      "line 1, column 7: 7 digits don't make whole 3×2 layers of 6"
+    );
+    assert_eq!(
+      err("", 3, 2),
+      "line 1, column 1: 0 digits don't make whole 3×2 layers of 6"
+    );
+    assert_eq!(
+      err("12x4", 2, 2),
+      "line 1, column 3: expected a digit, found `x`"
+    );
+    assert_eq!(
+      err("12", 0, 2),
+      "line 1, column 1: a 0×2 layer holds no digits"
+    );
+  }
+}
@@ -1,13 +1,15 @@
+use crate::parse;
+
 fn fuel_for_mass(mass: u32) -> u32 {
   (mass / 3).saturating_sub(2)
 }
 
-fn masses<'a>(input: &'a str) -> impl Iterator<Item = u32> + 'a {
-  input.lines().flat_map(|line| line.parse())
+fn masses(input: &str) -> Result<Vec<u32>, parse::Error> {
+  parse::lines(input).map(|line| line.parse()).collect()
 }
 
-fn solve_01(input: &str) -> u32 {
-  masses(input).map(fuel_for_mass).sum()
+fn solve_01(masses: &[u32]) -> u32 {
+  masses.iter().copied().map(fuel_for_mass).sum()
 }
 
 fn total_fuel_for_mass(mass: u32) -> u32 {
@@ -22,18 +24,16 @@ fn total_fuel_for_mass(mass: u32) -> u32 {
   total
 }
 
-fn solve_02(input: &str) -> u32 {
-  masses(input).map(total_fuel_for_mass).sum()
+fn solve_02(masses: &[u32]) -> u32 {
+  masses.iter().copied().map(total_fuel_for_mass).sum()
 }
 
-pub fn solve(input: &str) {
-  let one = solve_01(input);
-
-  println!("part 1: {}", one);
-
-  let two = solve_02(input);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_01(&masses(input)?).to_string())
+}
 
-  println!("part 2: {}", two);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_02(&masses(input)?).to_string())
 }
 
 #[cfg(test)]
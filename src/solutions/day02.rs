@@ -1,4 +1,5 @@
 use crate::intcode::*;
+use crate::parse;
 
 fn run(mem: &[isize], noun: isize, verb: isize) -> isize {
   let mut vm = VM::new(mem);
@@ -17,7 +18,7 @@ fn solve_01(memory: &[isize]) -> isize {
 fn solve_02(memory: &[isize]) -> isize {
   for noun in 0..100 {
     for verb in 0..100 {
-      let res = run(&memory, noun, verb);
+      let res = run(memory, noun, verb);
 
       if res == 19_690_720 {
         return 100 * noun + verb;
@@ -28,18 +29,16 @@ fn solve_02(memory: &[isize]) -> isize {
   0
 }
 
-fn load_initial_memory(input: &str) -> Vec<isize> {
-  input.split(',').flat_map(|c| c.parse()).collect()
+fn load_initial_memory(input: &str) -> Result<Vec<isize>, parse::Error> {
+  parse::separated(input, ',')
 }
 
-pub fn solve(input: &str) {
-  let mem = load_initial_memory(input);
-
-  let res = solve_01(&mem);
-  println!("part 01: {}", res);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_01(&load_initial_memory(input)?).to_string())
+}
 
-  let res = solve_02(&mem);
-  println!("part 02: {}", res);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_02(&load_initial_memory(input)?).to_string())
 }
 
 #[cfg(test)]
@@ -47,17 +46,17 @@ mod tests {
   use super::*;
   #[test]
   fn part_one() {
-    let ops = load_initial_memory("1,9,10,3,2,3,11,0,99,30,40,50");
+    let ops = load_initial_memory("1,9,10,3,2,3,11,0,99,30,40,50").unwrap();
     let res = run(&ops, 9, 10);
 
     assert_eq!(res, 3500);
 
-    let ops = load_initial_memory("1,1,1,4,99,5,6,0,99");
+    let ops = load_initial_memory("1,1,1,4,99,5,6,0,99").unwrap();
     let res = run(&ops, 1, 1);
 
     assert_eq!(res, 30);
 
-    let ops = load_initial_memory("1,0,0,0,99");
+    let ops = load_initial_memory("1,0,0,0,99").unwrap();
     let res = run(&ops, 0, 0);
 
     assert_eq!(res, 2);
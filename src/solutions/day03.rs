@@ -1,119 +1,86 @@
-use std::collections::{HashMap, HashSet};
-
-type Coordinate = (isize, isize);
-
-#[derive(Debug)]
-enum Dir {
-  Up,
-  Down,
-  Left,
-  Right,
-}
-
-impl Dir {
-  pub fn delta(&self) -> Coordinate {
-    match self {
-      Dir::Up => (0, 1),
-      Dir::Down => (0, -1),
-      Dir::Left => (-1, 0),
-      Dir::Right => (1, 0),
-    }
-  }
-}
-
-// in real world, we'd use TryFrom, but no malformed inputs here.
-impl From<char> for Dir {
-  fn from(c: char) -> Self {
-    match c {
-      'U' => Self::Up,
-      'D' => Self::Down,
-      'L' => Self::Left,
-      'R' => Self::Right,
-      _ => panic!("non-supported direction"),
-    }
+use crate::grid::Direction;
+use crate::parse::{self, Token};
+use crate::wire::{self, Crossing, Wire};
+use std::convert::TryFrom;
+
+fn direction(c: char) -> Option<Direction> {
+  match c {
+    'U' => Some(Direction::Up),
+    'D' => Some(Direction::Down),
+    'L' => Some(Direction::Left),
+    'R' => Some(Direction::Right),
+    _ => None,
   }
 }
 
 #[derive(Debug)]
 struct Step {
-  dir: Dir,
+  dir: Direction,
   len: usize,
 }
 
-impl From<&str> for Step {
-  fn from(s: &str) -> Self {
-    let dir = s.chars().nth(0).unwrap().into();
-    let len = s[1..].parse().unwrap();
+impl TryFrom<Token<'_>> for Step {
+  type Error = parse::Error;
 
-    Self { dir, len }
-  }
-}
-
-fn path(route: &[Step]) -> HashMap<Coordinate, isize> {
-  let mut map = HashMap::new();
-  let mut len = 1;
-
-  let (mut x, mut y) = (0, 0);
-
-  for step in route {
-    let (dx, dy) = step.dir.delta();
+  fn try_from(token: Token) -> Result<Self, parse::Error> {
+    let c = token
+      .text
+      .chars()
+      .next()
+      .ok_or_else(|| token.error("expected a step like `R8`"))?;
+    let dir =
+      direction(c).ok_or_else(|| token.error(format!("expected U, D, L or R, found `{}`", c)))?;
+    let len = token.slice(c.len_utf8()..token.text.len()).parse()?;
 
-    for _ in 0..step.len {
-      x += dx;
-      y += dy;
-
-      *map.entry((x, y)).or_insert(0) = len;
-      len += 1;
-    }
+    Ok(Self { dir, len })
   }
-
-  map
-}
-
-fn manhattan_distance(coord: Coordinate) -> isize {
-  coord.0.abs() + coord.1.abs()
 }
 
-fn solve_01(routes: &[Vec<Step>]) -> isize {
-  let path_one = path(&routes[0]);
-  let path_two = path(&routes[1]);
+fn crossings(routes: &[Vec<Step>]) -> Vec<Crossing> {
+  let wires: Vec<Wire> = routes
+    .iter()
+    .map(|route| Wire::new(route.iter().map(|step| (step.dir, step.len))))
+    .collect();
 
-  let set_one: HashSet<_> = path_one.keys().collect();
-  let set_two: HashSet<_> = path_two.keys().collect();
-  let intersections = set_one.intersection(&set_two);
+  // two runs of one wire crossing doesn't count.
+  wire::crossings(&wires)
+    .into_iter()
+    .filter(|crossing| !crossing.is_self())
+    .collect()
+}
 
-  intersections
-    .map(|x| manhattan_distance(**x))
+// `None` when no two wires cross.
+fn solve_01(routes: &[Vec<Step>]) -> Option<isize> {
+  crossings(routes)
+    .iter()
+    .map(|crossing| crossing.distance)
     .min()
-    .unwrap()
 }
 
-fn solve_02(routes: &[Vec<Step>]) -> isize {
-  let path_one = path(&routes[0]);
-  let path_two = path(&routes[1]);
+fn solve_02(routes: &[Vec<Step>]) -> Option<isize> {
+  crossings(routes).iter().map(Crossing::steps).min()
+}
 
-  let set_one: HashSet<_> = path_one.keys().collect();
-  let set_two: HashSet<_> = path_two.keys().collect();
-  let intersections = set_one.intersection(&set_two);
+fn parse(input: &str) -> Result<Vec<Vec<Step>>, parse::Error> {
+  parse::lines(input)
+    .map(|line| line.split(',').map(Step::try_from).collect())
+    .collect()
+}
 
-  intersections
-    .map(|x| path_one.get(*x).unwrap() + path_two.get(*x).unwrap())
-    .min()
-    .unwrap()
+fn never_cross() -> parse::Error {
+  parse::Error::new("the wires never cross")
 }
 
-fn parse(input: &str) -> Vec<Vec<Step>> {
-  input
-    .lines()
-    .map(|line| line.trim().split(',').map(Step::from).collect())
-    .collect()
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let res = solve_01(&parse(input)?).ok_or_else(never_cross)?;
+
+  Ok(res.to_string())
 }
 
-pub fn solve(input: &str) {
-  let instructions = parse(input);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let res = solve_02(&parse(input)?).ok_or_else(never_cross)?;
 
-  println!("part one: {}", solve_01(&instructions));
-  println!("part two: {}", solve_02(&instructions));
+  Ok(res.to_string())
 }
 
 #[cfg(test)]
@@ -124,11 +91,12 @@ mod tests {
     let input = parse(
       "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
       U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
-    );
+    )
+    .unwrap();
 
     let res = solve_01(&input);
 
-    assert_eq!(res, 135);
+    assert_eq!(res, Some(135));
   }
 
   #[test]
@@ -136,10 +104,40 @@ mod tests {
     let input = parse(
       "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
       U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
-    );
+    )
+    .unwrap();
 
     let res = solve_02(&input);
 
-    assert_eq!(res, 410);
+    assert_eq!(res, Some(410));
+  }
+
+  #[test]
+  fn bad_steps() {
+    let err = |input| parse(input).unwrap_err().to_string();
+
+    assert_eq!(
+      err("R8,U5\nR8,X5"),
+      "line 2, column 4: expected U, D, L or R, found `X`"
+    );
+    assert_eq!(
+      err("R8,Ux"),
+      "line 1, column 5: `x`: invalid digit found in string"
+    );
+    assert_eq!(err("R8,,U5"), "line 1, column 4: expected a step like `R8`");
+  }
+
+  #[test]
+  fn no_crossings() {
+    for &input in &["", "R8,U5,L5,D3", "R8,U5\nL8,D5", "R2,L2\nU2,D2"] {
+      assert_eq!(
+        super::part_one(input).unwrap_err().to_string(),
+        "the wires never cross"
+      );
+      assert_eq!(
+        super::part_two(input).unwrap_err().to_string(),
+        "the wires never cross"
+      );
+    }
   }
 }
@@ -1,82 +1,230 @@
+use crate::parse::{self, Token};
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 
-fn is_sorted<T: PartialOrd>(slice: &[T]) -> bool {
-  if slice.is_empty() {
-    return true;
-  }
+// a check fed one digit at a time, most significant first, so counting can share work
+// between every number that leaves a rule in the same state.
+trait Rule {
+  fn start(&self) -> u32;
+
+  // `None` rejects the number whatever digits follow.
+  fn next(&self, state: u32, digit: u32) -> Option<u32>;
 
-  let mut last = &slice[0];
+  fn accepts(&self, state: u32) -> bool;
+}
 
-  for i in slice {
-    if i < last {
-      return false;
+// states hold the last digit plus one, 0 before the first.
+struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+  fn start(&self) -> u32 {
+    0
+  }
+
+  fn next(&self, last: u32, digit: u32) -> Option<u32> {
+    if digit + 1 >= last {
+      Some(digit + 1)
+    } else {
+      None
     }
-    last = i;
   }
 
-  true
+  fn accepts(&self, _: u32) -> bool {
+    true
+  }
 }
 
-fn digits(i: usize) -> Vec<usize> {
-  let mut m = Vec::with_capacity(6);
+// some run of equal digits at least `min` long and at most `max` long.
+// states pack the last digit plus one, the current run's length, and whether a run fit.
+struct Run {
+  min: u32,
+  max: u32,
+}
 
-  let mut n = i;
-  while n > 0 {
-    m.push(n % 10);
-    n /= 10;
+impl Run {
+  // two adjacent digits are the same.
+  fn pair() -> Run {
+    Run {
+      min: 2,
+      max: u32::MAX,
+    }
   }
 
-  m.reverse();
+  // a pair that isn't part of a longer run.
+  fn exact_pair() -> Run {
+    Run { min: 2, max: 2 }
+  }
 
-  m
+  fn fits(&self, len: u32) -> bool {
+    self.min <= len && len <= self.max
+  }
 }
 
-fn check_01(i: usize) -> bool {
-  let v: Vec<_> = digits(i);
+impl Rule for Run {
+  fn start(&self) -> u32 {
+    0
+  }
 
-  if is_sorted(&v) {
-    let mut h = HashMap::new();
+  fn next(&self, state: u32, digit: u32) -> Option<u32> {
+    let (last, len, found) = (state & 0xf, (state >> 4) & 0xff, state >> 12);
 
-    for c in v {
-      *h.entry(c).or_insert(0) += 1;
-    }
+    let (len, found) = if last == digit + 1 {
+      // past max + 1 the run's length no longer matters.
+      ((len + 1).min(self.max.saturating_add(1)).min(0xff), found)
+    } else {
+      (1, found | self.fits(len) as u32)
+    };
 
-    h.values().any(|&v| v >= 2)
-  } else {
-    false
+    Some((digit + 1) | (len << 4) | (found << 12))
   }
+
+  fn accepts(&self, state: u32) -> bool {
+    state >> 12 == 1 || self.fits((state >> 4) & 0xff)
+  }
+}
+
+struct Passwords {
+  rules: Vec<Box<dyn Rule>>,
 }
 
-fn solve_01(range: std::ops::RangeInclusive<usize>) -> usize {
-  range.filter(|&i| check_01(i)).count()
+fn digits(n: u64) -> Vec<u32> {
+  n.to_string()
+    .chars()
+    .map(|c| c.to_digit(10).unwrap())
+    .collect()
 }
 
-fn check_02(i: usize) -> bool {
-  let v: Vec<_> = digits(i);
+impl Passwords {
+  fn new(rules: Vec<Box<dyn Rule>>) -> Passwords {
+    Passwords { rules }
+  }
+
+  fn start(&self) -> Vec<u32> {
+    self.rules.iter().map(|rule| rule.start()).collect()
+  }
+
+  fn feed(&self, states: &[u32], digit: u32) -> Option<Vec<u32>> {
+    self
+      .rules
+      .iter()
+      .zip(states)
+      .map(|(rule, &state)| rule.next(state, digit))
+      .collect()
+  }
+
+  fn accepts(&self, states: &[u32]) -> bool {
+    self
+      .rules
+      .iter()
+      .zip(states)
+      .all(|(rule, &state)| rule.accepts(state))
+  }
+
+  fn check(&self, n: u64) -> bool {
+    digits(n)
+      .into_iter()
+      .try_fold(self.start(), |states, digit| self.feed(&states, digit))
+      .is_some_and(|states| self.accepts(&states))
+  }
+
+  // ways to finish a number with `left` more digits of any value.
+  fn free(&self, left: usize, states: Vec<u32>, memo: &mut HashMap<(usize, Vec<u32>), u64>) -> u64 {
+    if left == 0 {
+      return self.accepts(&states) as u64;
+    }
+
+    if let Some(&count) = memo.get(&(left, states.clone())) {
+      return count;
+    }
+
+    let count = (0..10)
+      .filter_map(|digit| self.feed(&states, digit))
+      .map(|next| self.free(left - 1, next, memo))
+      .sum();
+
+    memo.insert((left, states), count);
+    count
+  }
+
+  // passwords in 1..=n.
+  fn up_to(&self, n: u64, memo: &mut HashMap<(usize, Vec<u32>), u64>) -> u64 {
+    if n == 0 {
+      return 0;
+    }
+
+    let digits = digits(n);
+    let mut count = 0;
+
+    // every shorter number is below n.
+    for len in 1..digits.len() {
+      for first in 1..10 {
+        if let Some(states) = self.feed(&self.start(), first) {
+          count += self.free(len - 1, states, memo);
+        }
+      }
+    }
+
+    // then the ones as long as n, going under it at position i.
+    let mut states = self.start();
 
-  let mut s = v.clone();
-  s.sort();
+    for (i, &digit) in digits.iter().enumerate() {
+      let lowest = if i == 0 { 1 } else { 0 };
 
-  if v == s {
-    let mut h = HashMap::new();
+      for below in lowest..digit {
+        if let Some(next) = self.feed(&states, below) {
+          count += self.free(digits.len() - i - 1, next, memo);
+        }
+      }
 
-    for c in v {
-      *h.entry(c).or_insert(0) += 1;
+      match self.feed(&states, digit) {
+        Some(next) => states = next,
+        None => return count,
+      }
     }
 
-    h.values().any(|&v| v == 2)
-  } else {
-    false
+    count + self.accepts(&states) as u64
+  }
+
+  fn count(&self, range: RangeInclusive<u64>) -> u64 {
+    let (&lo, &hi) = (range.start(), range.end());
+
+    if lo > hi {
+      return 0;
+    }
+
+    let mut memo = HashMap::new();
+    let zero = (lo == 0 && self.check(0)) as u64;
+
+    self.up_to(hi, &mut memo) - self.up_to(lo.saturating_sub(1), &mut memo) + zero
+  }
+}
+
+fn rules_01() -> Passwords {
+  Passwords::new(vec![Box::new(NonDecreasing), Box::new(Run::pair())])
+}
+
+fn rules_02() -> Passwords {
+  Passwords::new(vec![Box::new(NonDecreasing), Box::new(Run::exact_pair())])
+}
+
+fn parse(input: &str) -> Result<RangeInclusive<u64>, parse::Error> {
+  let token = Token::from(input).trim();
+  let (lo, hi) = token.split_once('-')?;
+  let (lo, hi) = (lo.parse()?, hi.parse()?);
+
+  if lo > hi {
+    return Err(token.error(format!("the range `{}` is backwards", token.text)));
   }
+
+  Ok(lo..=hi)
 }
 
-fn solve_02(range: std::ops::RangeInclusive<usize>) -> usize {
-  range.filter(|&i| check_02(i)).count()
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(rules_01().count(parse(input)?).to_string())
 }
 
-pub fn solve(_: &str) {
-  println!("part one: {:?}", solve_01(248_345..=746_315));
-  println!("part one: {:?}", solve_02(248_345..=746_315));
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(rules_02().count(parse(input)?).to_string())
 }
 
 #[cfg(test)]
@@ -84,15 +232,53 @@ mod tests {
   use super::*;
   #[test]
   fn part_one() {
-    assert!(check_01(111_111));
-    assert!(!check_01(223_450));
-    assert!(!check_01(123_789));
+    let rules = rules_01();
+
+    assert!(rules.check(111_111));
+    assert!(!rules.check(223_450));
+    assert!(!rules.check(123_789));
   }
 
   #[test]
   fn part_two() {
-    assert!(check_02(112_233));
-    assert!(!check_02(123_444));
-    assert!(check_02(111_122));
+    let rules = rules_02();
+
+    assert!(rules.check(112_233));
+    assert!(!rules.check(123_444));
+    assert!(rules.check(111_122));
+  }
+
+  #[test]
+  fn counts_like_checking() {
+    for rules in &[rules_01(), rules_02()] {
+      for &(lo, hi) in &[(0, 2_000), (248_345, 260_000), (99, 100), (5, 5)] {
+        let brute = (lo..=hi).filter(|&n| rules.check(n)).count() as u64;
+
+        assert_eq!(rules.count(lo..=hi), brute, "{}..={}", lo, hi);
+      }
+    }
+  }
+
+  #[test]
+  fn huge_ranges() {
+    // non-decreasing numbers can't hold a 0, and there are C(k + 8, 8) with k digits.
+    let sorted = Passwords::new(vec![Box::new(NonDecreasing)]);
+    assert_eq!(sorted.count(1..=999_999_999_999_999_999), 4_686_824);
+
+    let total = rules_01().count(0..=u64::MAX);
+    assert!(total > rules_02().count(0..=u64::MAX));
+  }
+
+  #[test]
+  fn ranges() {
+    assert_eq!(parse("248345-746315\n").unwrap(), 248_345..=746_315);
+    assert_eq!(
+      parse("9-x").unwrap_err().to_string(),
+      "line 1, column 3: `x`: invalid digit found in string"
+    );
+    assert_eq!(
+      parse("9-1").unwrap_err().to_string(),
+      "line 1, column 1: the range `9-1` is backwards"
+    );
   }
 }
@@ -1,23 +1,22 @@
 use crate::intcode::*;
+use crate::parse;
 
-fn load_initial_memory(input: &str) -> Vec<isize> {
-  input.split(',').flat_map(|c| c.trim().parse()).collect()
+fn load_initial_memory(input: &str) -> Result<Vec<isize>, parse::Error> {
+  parse::separated(input, ',')
 }
 
-pub fn solve(input: &str) {
-  let mem = load_initial_memory(input);
-
-  let mut vm = VM::new(&mem);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let mut vm = VM::new(&load_initial_memory(input)?);
   vm.input.send(1).unwrap();
-  let res = vm.run();
 
-  println!("part one: {}", res);
+  Ok(vm.run().to_string())
+}
 
-  let mut vm = VM::new(&mem);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let mut vm = VM::new(&load_initial_memory(input)?);
   vm.input.send(5).unwrap();
-  let res = vm.run();
 
-  println!("part two: {}", res);
+  Ok(vm.run().to_string())
 }
 
 #[cfg(test)]
@@ -30,7 +29,8 @@ mod tests {
       20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,
       1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,
       1,46,98,99",
-    );
+    )
+    .unwrap();
     let mut vm = VM::new(&mem);
     vm.input.send(5).unwrap();
     let res = vm.run();
@@ -1,45 +1,84 @@
-use std::collections::{HashMap, HashSet};
+use crate::graph::{Graph, NotATree, Tree};
+use crate::parse::{self, Token};
 
-fn to_graph(input: &str) -> HashMap<&str, &str> {
-  input
-    .lines()
-    .map(|line| {
-      let mut parts = line.trim().split(')');
+// an edge from each body to the ones orbiting it.
+fn to_graph(input: &str) -> Result<Graph, parse::Error> {
+  let mut graph = Graph::new();
 
-      let a = parts.next().expect("aoc input");
-      let b = parts.next().expect("aoc input");
+  for line in parse::lines(input) {
+    let (a, b) = line.split_once(')')?;
+    graph.add_edge(a.text, b.text, 1);
+  }
 
-      (b, a)
-    })
-    .collect()
+  Ok(graph)
 }
 
-fn path_to_root<'a>(key: &'a str, graph: &'a HashMap<&str, &str>) -> Vec<&'a str> {
-  std::iter::successors(Some(key), |k| graph.get(*k).copied()).collect()
+// the first `a)b` line that `f` picks.
+fn orbit_line<'a, F: Fn(&str, &str) -> bool>(input: &'a str, f: F) -> Option<Token<'a>> {
+  parse::lines(input).find(|line| {
+    line
+      .split_once(')')
+      .ok()
+      .is_some_and(|(a, b)| f(a.text, b.text))
+  })
 }
 
-fn solve_01(graph: &HashMap<&str, &str>) -> usize {
-  graph
-    .keys()
-    .map(|key| path_to_root(key, graph).len() - 1)
+fn orbits<'a>(graph: &'a Graph, input: &str) -> Result<Tree<'a>, parse::Error> {
+  let com = graph
+    .node("COM")
+    .ok_or_else(|| parse::Error::new("nothing orbits COM"))?;
+
+  graph.tree(com).map_err(|e| {
+    let (line, message) = match e {
+      NotATree::Revisited { from, to } => {
+        let (from, to) = (graph.name(from), graph.name(to));
+        let line = orbit_line(input, |a, b| a == from && b == to);
+
+        (line, format!("`{}` already orbits something else", to))
+      }
+      NotATree::Unreached(body) => {
+        let body = graph.name(body);
+        let line = orbit_line(input, |a, b| a == body || b == body);
+
+        (line, format!("`{}` isn't connected to COM", body))
+      }
+    };
+
+    match line {
+      Some(line) => line.error(message),
+      None => parse::Error::new(message),
+    }
+  })
+}
+
+fn solve_01(orbits: &Tree) -> usize {
+  orbits
+    .subtree(orbits.root())
+    .iter()
+    .map(|&body| orbits.depth(body))
     .sum()
 }
 
-fn solve_02(graph: &HashMap<&str, &str>) -> usize {
-  let me = path_to_root("YOU", graph);
-  let santa = path_to_root("SAN", graph);
+// transfers between the bodies YOU and SAN orbit, not between YOU and SAN themselves.
+fn solve_02(graph: &Graph, orbits: &Tree) -> Option<usize> {
+  let me = orbits.parent(graph.node("YOU")?)?;
+  let santa = orbits.parent(graph.node("SAN")?)?;
+
+  Some(orbits.distance(me, santa))
+}
 
-  let me: HashSet<_> = me.iter().collect();
-  let santa: HashSet<_> = santa.iter().collect();
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let graph = to_graph(input)?;
 
-  me.symmetric_difference(&santa).count() - 2
+  Ok(solve_01(&orbits(&graph, input)?).to_string())
 }
 
-pub fn solve(input: &str) {
-  let graph = to_graph(input);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let graph = to_graph(input)?;
+  let transfers = solve_02(&graph, &orbits(&graph, input)?)
+    .ok_or_else(|| parse::Error::new("YOU and SAN both need to orbit something"))?;
 
-  println!("part one: {}", solve_01(&graph));
-  println!("part two: {}", solve_02(&graph));
+  Ok(transfers.to_string())
 }
 
 #[cfg(test)]
@@ -59,9 +98,10 @@ mod tests {
     E)J
     J)K
     K)L",
-    );
+    )
+    .unwrap();
 
-    assert_eq!(solve_01(&graph), 42);
+    assert_eq!(solve_01(&orbits(&graph, "").unwrap()), 42);
   }
 
   #[test]
@@ -80,8 +120,30 @@ mod tests {
       K)L
       K)YOU
       I)SAN",
-    );
+    )
+    .unwrap();
 
-    assert_eq!(solve_02(&graph), 4);
+    assert_eq!(solve_02(&graph, &orbits(&graph, "").unwrap()), Some(4));
+  }
+
+  #[test]
+  fn bad_orbits() {
+    let err = |input| super::part_one(input).unwrap_err().to_string();
+
+    assert_eq!(err("COM)A\nA-B"), "line 2, column 1: expected `)` in `A-B`");
+    assert_eq!(err("X)A"), "nothing orbits COM");
+    assert_eq!(
+      err("COM)A\nB)C"),
+      "line 2, column 1: `B` isn't connected to COM"
+    );
+    assert_eq!(
+      err("COM)A\n  COM)B\n  B)A"),
+      "line 3, column 3: `A` already orbits something else"
+    );
+    assert_eq!(
+      err("COM)A\nX)Y\nY)X"),
+      "line 2, column 1: `X` isn't connected to COM"
+    );
+    assert!(super::part_two("COM)A\nA)YOU").is_err());
   }
 }
@@ -1,115 +1,178 @@
 use crate::intcode::*;
+use crate::parse;
+use crate::solutions::workers;
+use crossbeam::channel;
 use itertools::*;
 use std::thread;
 
-fn run_01(mem: &[isize], initial: &[isize]) -> isize {
-  let mut vm_01 = VM::new(mem);
-  let mut vm_02 = VM::new(mem);
-  let mut vm_03 = VM::new(mem);
-  let mut vm_04 = VM::new(mem);
-  let mut vm_05 = VM::new(mem);
-
-  vm_01.pipe(&mut vm_02);
-  vm_02.pipe(&mut vm_03);
-  vm_03.pipe(&mut vm_04);
-  vm_04.pipe(&mut vm_05);
-
-  vm_01.input.send(initial[0]).unwrap();
-  vm_01.input.send(0).unwrap();
-
-  vm_02.input.send(initial[1]).unwrap();
-  vm_03.input.send(initial[2]).unwrap();
-  vm_04.input.send(initial[3]).unwrap();
-  vm_05.input.send(initial[4]).unwrap();
-
-  thread::spawn(move || vm_01.run());
-  thread::spawn(move || vm_02.run());
-  thread::spawn(move || vm_03.run());
-  thread::spawn(move || vm_04.run());
-  thread::spawn(move || vm_05.run()).join().unwrap()
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Topology {
+  Chain,
+  // the last amp's output goes back into the first until they all halt.
+  Feedback,
 }
 
-fn solve_01(mem: &[isize]) -> isize {
-  (0..5)
-    .permutations(5)
-    .map(|permutation| run_01(mem, &permutation))
-    .max()
-    .expect("has a max")
+#[derive(Debug, Clone)]
+pub struct Amplifiers {
+  pub count: usize,
+  // each amp gets a different one of these.
+  pub phases: Vec<isize>,
+  pub topology: Topology,
+}
+
+impl Amplifiers {
+  // the last amp's final output, with 0 sent into the first, and 0 with no amps at all.
+  pub fn run(&self, mem: &[isize], ordering: &[isize]) -> isize {
+    if ordering.is_empty() {
+      return 0;
+    }
+
+    let mut vms: Vec<VM> = ordering.iter().map(|_| VM::new(mem)).collect();
+
+    for i in 1..vms.len() {
+      let (before, after) = vms.split_at_mut(i);
+      before[i - 1].pipe(&mut after[0]);
+    }
+
+    if self.topology == Topology::Feedback {
+      match vms.split_first_mut() {
+        Some((first, [])) => first.loopback(),
+        Some((first, rest)) => rest.last_mut().unwrap().pipe(first),
+        None => {}
+      }
+    }
+
+    for (vm, &phase) in vms.iter().zip(ordering) {
+      vm.input.send(phase).unwrap();
+    }
+    vms[0].input.send(0).unwrap();
+
+    let mut handles: Vec<_> = vms
+      .into_iter()
+      .map(|mut vm| thread::spawn(move || vm.run()))
+      .collect();
+
+    handles.pop().unwrap().join().unwrap()
+  }
+
+  // the strongest signal and the phase ordering that makes it, trying orderings on `workers` threads.
+  // ties go to the first ordering, `None` when there are fewer phases than amps.
+  pub fn optimize(&self, mem: &[isize], workers: usize) -> Option<(isize, Vec<isize>)> {
+    let (sender, receiver) = channel::bounded(workers * 2);
+
+    crossbeam::scope(|scope| {
+      let handles: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+          let receiver = receiver.clone();
+
+          scope.spawn(move |_| {
+            receiver
+              .iter()
+              .map(|(n, ordering): (usize, Vec<isize>)| {
+                (self.run(mem, &ordering), std::cmp::Reverse(n), ordering)
+              })
+              .max()
+          })
+        })
+        .collect();
+
+      for job in self
+        .phases
+        .iter()
+        .copied()
+        .permutations(self.count)
+        .enumerate()
+      {
+        sender.send(job).unwrap();
+      }
+      drop(sender);
+
+      handles
+        .into_iter()
+        .filter_map(|handle| handle.join().unwrap())
+        .max()
+        .map(|(signal, _, ordering)| (signal, ordering))
+    })
+    .unwrap()
+  }
 }
 
-fn run_02(mem: &[isize], initial: &[isize]) -> isize {
-  let mut vm_01 = VM::new(mem);
-  let mut vm_02 = VM::new(mem);
-  let mut vm_03 = VM::new(mem);
-  let mut vm_04 = VM::new(mem);
-  let mut vm_05 = VM::new(mem);
-
-  vm_01.pipe(&mut vm_02);
-  vm_02.pipe(&mut vm_03);
-  vm_03.pipe(&mut vm_04);
-  vm_04.pipe(&mut vm_05);
-  vm_05.pipe(&mut vm_01);
-
-  vm_01.input.send(initial[0]).unwrap();
-  vm_01.input.send(0).unwrap();
-
-  vm_02.input.send(initial[1]).unwrap();
-  vm_03.input.send(initial[2]).unwrap();
-  vm_04.input.send(initial[3]).unwrap();
-  vm_05.input.send(initial[4]).unwrap();
-
-  thread::spawn(move || vm_01.run());
-  thread::spawn(move || vm_02.run());
-  thread::spawn(move || vm_03.run());
-  thread::spawn(move || vm_04.run());
-  thread::spawn(move || vm_05.run()).join().unwrap()
+fn solve_01(mem: &[isize]) -> isize {
+  let amps = Amplifiers {
+    count: 5,
+    phases: (0..5).collect(),
+    topology: Topology::Chain,
+  };
+
+  amps.optimize(mem, workers()).expect("has a max").0
 }
 
 fn solve_02(mem: &[isize]) -> isize {
-  (5..10)
-    .permutations(5)
-    .map(|permutation| run_02(mem, &permutation))
-    .max()
-    .expect("has a max")
+  let amps = Amplifiers {
+    count: 5,
+    phases: (5..10).collect(),
+    topology: Topology::Feedback,
+  };
+
+  amps.optimize(mem, workers()).expect("has a max").0
 }
 
-fn load_initial_memory(input: &str) -> Vec<isize> {
-  input.split(',').flat_map(|c| c.trim().parse()).collect()
+fn load_initial_memory(input: &str) -> Result<Vec<isize>, parse::Error> {
+  parse::separated(input, ',')
 }
 
-pub fn solve(input: &str) {
-  let mem = load_initial_memory(input);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_01(&load_initial_memory(input)?).to_string())
+}
 
-  dbg!(solve_01(&mem));
-  dbg!(solve_02(&mem));
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_02(&load_initial_memory(input)?).to_string())
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  fn chain() -> Amplifiers {
+    Amplifiers {
+      count: 5,
+      phases: (0..5).collect(),
+      topology: Topology::Chain,
+    }
+  }
+
+  fn feedback() -> Amplifiers {
+    Amplifiers {
+      count: 5,
+      phases: (5..10).collect(),
+      topology: Topology::Feedback,
+    }
+  }
+
   #[test]
   fn part_one() {
-    let mem = load_initial_memory("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+    let mem = load_initial_memory("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").unwrap();
     let inputs = vec![4, 3, 2, 1, 0];
-    let last = run_01(&mem, &inputs);
+    let last = chain().run(&mem, &inputs);
     assert_eq!(last, 43_210);
 
     let mem = load_initial_memory(
       "3,23,3,24,1002,24,10,24,1002,23,
       -1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
-    );
+    )
+    .unwrap();
     let inputs = vec![0, 1, 2, 3, 4];
-    let last = run_01(&mem, &inputs);
+    let last = chain().run(&mem, &inputs);
 
     assert_eq!(last, 54_321);
 
     let mem = load_initial_memory(
       "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,
       31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
-    );
+    )
+    .unwrap();
     let inputs = vec![1, 0, 4, 3, 2];
-    let last = run_01(&mem, &inputs);
+    let last = chain().run(&mem, &inputs);
 
     assert_eq!(last, 65_210);
   }
@@ -120,21 +183,56 @@ mod tests {
       "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,
       -5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,
       53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
-    );
+    )
+    .unwrap();
     let inputs = vec![9, 7, 8, 5, 6];
 
-    let res = run_02(&mem, &inputs);
+    let res = feedback().run(&mem, &inputs);
 
     assert_eq!(res, 18_216);
 
     let mem = load_initial_memory(
       "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,
       27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
-    );
+    )
+    .unwrap();
     let inputs = vec![9, 8, 7, 6, 5];
 
-    let res = run_02(&mem, &inputs);
+    let res = feedback().run(&mem, &inputs);
 
     assert_eq!(res, 139_629_729);
   }
+
+  #[test]
+  fn optimizes() {
+    let mem = load_initial_memory("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").unwrap();
+
+    assert_eq!(
+      chain().optimize(&mem, 4),
+      Some((43_210, vec![4, 3, 2, 1, 0]))
+    );
+
+    let three = Amplifiers {
+      count: 3,
+      ..chain()
+    };
+    assert_eq!(three.optimize(&mem, 1), Some((432, vec![4, 3, 2])));
+
+    let too_many = Amplifiers {
+      count: 6,
+      ..chain()
+    };
+    assert_eq!(too_many.optimize(&mem, 2), None);
+
+    let mem = load_initial_memory(
+      "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,
+      27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+    )
+    .unwrap();
+
+    assert_eq!(
+      feedback().optimize(&mem, 3),
+      Some((139_629_729, vec![9, 8, 7, 6, 5]))
+    );
+  }
 }
@@ -1,78 +1,69 @@
-use std::fmt;
+use crate::grid::DenseGrid;
+use crate::ocr;
+use crate::parse;
+use crate::sif::SifImage;
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum Pixel {
-  Transparent,
-  Black,
-  White,
-}
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
 
-impl From<char> for Pixel {
-  fn from(c: char) -> Pixel {
-    match c {
-      '0' => Pixel::Black,
-      '1' => Pixel::White,
-      '2' => Pixel::Transparent,
-      _ => unreachable!(),
-    }
-  }
+// 0 is black.
+const WHITE: u8 = 1;
+const TRANSPARENT: u8 = 2;
+
+fn lit(image: &SifImage) -> DenseGrid<bool> {
+  image.composite(TRANSPARENT).map(|&d| d == WHITE)
 }
 
-impl fmt::Display for Pixel {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    match self {
-      Pixel::White => write!(f, "▒"),
-      _ => write!(f, " "),
-    }
-  }
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(
+    SifImage::parse(input, WIDTH, HEIGHT)?
+      .checksum()
+      .to_string(),
+  )
 }
 
-impl Pixel {
-  fn paint(self, layer: Pixel) -> Pixel {
-    match self {
-      Pixel::Transparent => layer,
-      n => n,
-    }
-  }
+pub fn picture(input: &str) -> Result<DenseGrid<bool>, parse::Error> {
+  Ok(lit(&SifImage::parse(input, WIDTH, HEIGHT)?))
 }
 
-fn solve_01(input: &[Pixel], width: usize, height: usize) -> usize {
-  let m = input
-    .chunks_exact(width * height)
-    .min_by_key(|c| c.iter().filter(|&&d| d == Pixel::Black).count())
-    .unwrap();
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let lit = picture(input)?;
 
-  m.iter().filter(|d| **d == Pixel::White).count()
-    * m.iter().filter(|d| **d == Pixel::Black).count()
+  Ok(ocr::read(&lit).unwrap_or_else(|| lit.render(|&p| if p { '▒' } else { ' ' })))
 }
 
-fn solve_02(input: &[Pixel], width: usize, height: usize) -> Vec<Pixel> {
-  input
-    .chunks_exact(width * height)
-    .fold(vec![Pixel::Transparent; width * height], |acc, layer| {
-      acc
-        .iter()
-        .zip(layer)
-        .map(|(&curr, &layer)| curr.paint(layer))
-        .collect()
-    })
-}
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-fn input_gen(input: &str) -> Vec<Pixel> {
-  input.chars().map(|c| c.into()).collect()
-}
+  #[test]
+  fn part_two() {
+    let img = lit(&SifImage::parse("0222112222120000", 2, 2).unwrap());
+
+    assert_eq!(img.render(|&p| if p { '▒' } else { ' ' }), " ▒\n▒ ");
+  }
 
-pub fn solve(input: &str) {
-  let r = input_gen(input);
+  #[test]
+  fn reads_letters() {
+    let rows = [
+      "#..#.####.#....#.....##..",
+      "#..#.#....#....#....#..#.",
+      "####.###..#....#....#..#.",
+      "#..#.#....#....#....#..#.",
+      "#..#.#....#....#....#..#.",
+      "#..#.####.####.####..##..",
+    ];
 
-  dbg!(solve_01(&r, 25, 6));
+    // a fully transparent layer on top of the letters.
+    let input = "2".repeat(150) + &rows.concat().replace('#', "1").replace('.', "0");
 
-  let img = solve_02(&r, 25, 6);
+    assert_eq!(super::part_two(&input).unwrap(), "HELLO");
+  }
+
+  #[test]
+  fn bad_length() {
+    let err = super::part_one(&"0".repeat(151)).unwrap_err();
 
-  img.chunks(25).for_each(|row| {
-    for &pixel in row {
-      print!("{}", pixel);
-    }
-    println!();
-  })
+    assert_eq!(err.position, Some((1, 151)));
+  }
 }
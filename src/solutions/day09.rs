@@ -1,32 +1,32 @@
 use crate::intcode::*;
+use crate::parse;
+use std::convert::TryFrom;
 
-pub fn solve(input: &str) {
-  let mut vm = VM::from(input);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let mut vm = VM::try_from(input)?;
   vm.input.send(1).unwrap();
 
-  let part_one = vm.run();
-
-  dbg!(part_one);
+  Ok(vm.run().to_string())
+}
 
-  let mut vm = VM::from(input);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let mut vm = VM::try_from(input)?;
   vm.input.send(2).unwrap();
 
-  let part_two = vm.run();
-
-  dbg!(part_two);
+  Ok(vm.run().to_string())
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  fn load_initial_memory(input: &str) -> Vec<isize> {
-    input.split(',').flat_map(|c| c.trim().parse()).collect()
-  }
-
   #[test]
   fn quine() {
-    let mem = load_initial_memory("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+    let mem = parse::separated(
+      "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+      ',',
+    )
+    .unwrap();
     let mut vm = VM::new(&mem);
     let res = vm.run();
 
@@ -36,7 +36,7 @@ mod tests {
 
   #[test]
   fn big_numbers() {
-    let mut vm = VM::from("104,1125899906842624,99");
+    let mut vm = VM::try_from("104,1125899906842624,99").unwrap();
     let res = vm.run();
 
     assert_eq!(res, 1_125_899_906_842_624);
@@ -44,7 +44,7 @@ mod tests {
 
   #[test]
   fn big_numbers_two() {
-    let mut vm = VM::from("1102,34915192,34915192,7,4,7,99,0");
+    let mut vm = VM::try_from("1102,34915192,34915192,7,4,7,99,0").unwrap();
     let res = vm.run();
 
     assert_eq!(res, 1_219_070_632_396_864);
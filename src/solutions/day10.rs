@@ -1,16 +1,13 @@
+use crate::export::{self, Rgb};
+use crate::grid::{DenseGrid, Point};
+use crate::math;
+use crate::parse;
+use crate::solutions::workers;
 use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 
-trait Distance<T> {
-  fn manhattan_distance(self, t: Self) -> T;
-}
-
-type Coordinate = (i32, i32);
-
-impl Distance<i32> for Coordinate {
-  fn manhattan_distance(self, to: Coordinate) -> i32 {
-    (self.0 - to.0).abs() + (self.1 - to.1).abs()
-  }
-}
+pub type Coordinate = Point<i32>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 struct Slope {
@@ -18,122 +15,270 @@ struct Slope {
   dy: i32,
 }
 
-fn parse_input(input: &str) -> Vec<Coordinate> {
-  input
-    .lines()
-    .enumerate()
-    .flat_map(|(row, line)| {
+// rows are the input's own lines, blank ones included.
+pub fn parse_input(input: &str) -> Result<Vec<Coordinate>, parse::Error> {
+  parse::lines(input)
+    .flat_map(|line| {
+      let row = line.line - 1;
+
       line
-        .trim()
         .chars()
         .enumerate()
-        .filter_map(|(col, c)| {
-          if c == '#' {
-            Some((col as i32, row as i32))
-          } else {
-            None
-          }
+        .filter_map(move |(col, c)| match c.text {
+          "#" => Some(Ok(Point::new(col as i32, row as i32))),
+          "." => None,
+          _ => Some(Err(c.error(format!("expected # or ., found `{}`", c.text)))),
         })
-        .collect_vec()
     })
-    .collect_vec()
-}
-
-fn gcd(mut m: i32, mut n: i32) -> i32 {
-  while m != 0 {
-    let old_m = m;
-    m = n % m;
-    n = old_m;
-  }
-
-  n.abs()
+    .collect()
 }
 
 impl Slope {
   fn from(from: Coordinate, to: Coordinate) -> Slope {
-    let dx = to.0 - from.0;
-    let dy = to.1 - from.1;
+    let Point { x: dx, y: dy } = to - from;
     if dx == 0 && dy == 0 {
       return Slope { dx, dy };
     }
-    let div = gcd(dx, dy);
+    let div = math::gcd(dx, dy);
     Slope {
       dx: dx / div,
       dy: dy / div,
     }
   }
 
-  fn angle(self) -> f32 {
-    use std::f32;
+  // 0 from straight up through the right, 1 from straight down through the left,
+  // with y growing downwards, and 2 for no direction at all.
+  fn half(self) -> u8 {
     if self.dx == 0 && self.dy == 0 {
-      return f32::INFINITY;
-    }
-    let dx = self.dx as f32;
-    let dy = self.dy as f32;
-    let ang = dy.atan2(dx).to_degrees();
-    if ang < -90. {
-      360. + ang
+      2
+    } else if self.dx > 0 || (self.dx == 0 && self.dy < 0) {
+      0
     } else {
-      ang
+      1
     }
   }
 }
 
+// clockwise from straight up, exactly, and the zero slope last.
+impl Ord for Slope {
+  fn cmp(&self, other: &Slope) -> Ordering {
+    self.half().cmp(&other.half()).then_with(|| {
+      // positive when `other` is clockwise of `self`.
+      let cross =
+        i64::from(self.dx) * i64::from(other.dy) - i64::from(self.dy) * i64::from(other.dx);
+
+      0.cmp(&cross)
+    })
+  }
+}
+
+impl PartialOrd for Slope {
+  fn partial_cmp(&self, other: &Slope) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+// how many others each asteroid sees, in the order given, with stations split across `workers` threads.
+pub fn visible_counts(asteroids: &[Coordinate], workers: usize) -> Vec<usize> {
+  let chunk = asteroids.len().div_ceil(workers.max(1)).max(1);
+
+  crossbeam::scope(|scope| {
+    let handles: Vec<_> = asteroids
+      .chunks(chunk)
+      .map(|stations| {
+        scope.spawn(move |_| {
+          stations
+            .iter()
+            .map(|&station| {
+              asteroids
+                .iter()
+                .filter(|&&a| a != station)
+                .map(|&a| Slope::from(station, a))
+                .collect::<HashSet<_>>()
+                .len()
+            })
+            .collect::<Vec<_>>()
+        })
+      })
+      .collect();
+
+    handles
+      .into_iter()
+      .flat_map(|handle| handle.join().unwrap())
+      .collect()
+  })
+  .unwrap()
+}
+
+// the asteroid seeing the most, the rightmost then the lowest one on a tie.
+pub fn best(asteroids: &[Coordinate], counts: &[usize]) -> Option<(usize, Coordinate)> {
+  counts.iter().copied().zip(asteroids.iter().copied()).max()
+}
+
 fn solve_01(asteroids: &[Coordinate]) -> (usize, Coordinate) {
-  asteroids
+  best(asteroids, &visible_counts(asteroids, workers())).unwrap()
+}
+
+// every asteroid's visible count at its place on the map, `None` where there's no asteroid.
+pub fn heatmap(asteroids: &[Coordinate], counts: &[usize]) -> DenseGrid<Option<usize>> {
+  let width = asteroids.iter().map(|a| a.x + 1).max().unwrap_or(0);
+  let height = asteroids.iter().map(|a| a.y + 1).max().unwrap_or(0);
+  let mut grid = DenseGrid::new(width as usize, height as usize, None);
+
+  for (a, &count) in asteroids.iter().zip(counts) {
+    grid.set(Point::new(a.x as isize, a.y as isize), Some(count));
+  }
+
+  grid
+}
+
+// counts right-aligned in columns, dots for empty space.
+pub fn render_heatmap(grid: &DenseGrid<Option<usize>>) -> String {
+  let width = grid
+    .cells()
     .iter()
-    .map(|from| {
-      (
-        asteroids
-          .iter()
-          .map(|to| Slope::from(*from, *to))
-          .unique()
-          .count()
-          - 1,
-        *from,
-      )
-    })
+    .flatten()
+    .map(|count| count.to_string().len())
     .max()
-    .unwrap()
+    .unwrap_or(1);
+
+  grid
+    .rows()
+    .map(|row| {
+      row
+        .iter()
+        .map(|cell| match cell {
+          Some(count) => format!("{:>1$}", count, width),
+          None => format!("{:>1$}", ".", width),
+        })
+        .join(" ")
+    })
+    .join("\n")
 }
 
-fn solve_02(coord: Coordinate, asteroids: &[Coordinate]) -> Vec<(f32, Coordinate)> {
-  let mut map = asteroids
+// black for nothing there, then dark red up to white for the best station.
+pub fn heat_palette(grid: &DenseGrid<Option<usize>>) -> impl Fn(&Option<usize>) -> Rgb {
+  let most = grid
+    .cells()
     .iter()
-    .map(|ast| {
-      let sl = Slope::from(coord, *ast);
-      (sl, *ast)
-    })
-    .into_group_map();
+    .flatten()
+    .copied()
+    .max()
+    .unwrap_or(0)
+    .max(1);
 
-  let mut res = map
-    .iter_mut()
-    .flat_map(|(s, points)| {
-      let ang = s.angle();
-      points.sort_by_key(|k| k.manhattan_distance(coord));
+  move |cell| match cell {
+    Some(count) => export::heat(*count as f64 / most as f64),
+    None => export::BLACK,
+  }
+}
 
-      points
-        .iter()
-        .enumerate()
-        .map(|(i, k)| (ang + (i as f32) * 360., *k))
-        .collect_vec()
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Sightlines {
+  // nearest first along each ray, clockwise from straight up.
+  pub visible: Vec<Coordinate>,
+  // with the nearest asteroid in the way.
+  pub blocked: Vec<(Coordinate, Coordinate)>,
+}
+
+pub fn sightlines(station: Coordinate, asteroids: &[Coordinate]) -> Sightlines {
+  let mut lines = Sightlines::default();
+
+  for ray in vaporize(station, asteroids).rays {
+    let mut ray = ray.into_iter();
+
+    if let Some(nearest) = ray.next() {
+      lines.visible.push(nearest);
+      lines.blocked.extend(ray.map(|hidden| (hidden, nearest)));
+    }
+  }
+
+  lines
+}
+
+// asteroids in the order the laser hits them, with the rotation each one goes in, from 1.
+pub struct Vaporization {
+  // nearest first, ordered clockwise from straight up.
+  rays: Vec<VecDeque<Coordinate>>,
+  ray: usize,
+  rotation: usize,
+}
+
+impl Iterator for Vaporization {
+  type Item = (usize, Coordinate);
+
+  fn next(&mut self) -> Option<(usize, Coordinate)> {
+    loop {
+      if self.ray == self.rays.len() {
+        self.rays.retain(|ray| !ray.is_empty());
+
+        if self.rays.is_empty() {
+          return None;
+        }
+
+        self.ray = 0;
+        self.rotation += 1;
+      }
+
+      self.ray += 1;
+
+      if let Some(hit) = self.rays[self.ray - 1].pop_front() {
+        return Some((self.rotation, hit));
+      }
+    }
+  }
+}
+
+pub fn vaporize(station: Coordinate, asteroids: &[Coordinate]) -> Vaporization {
+  let mut rays: BTreeMap<Slope, Vec<Coordinate>> = BTreeMap::new();
+
+  for &asteroid in asteroids.iter().filter(|&&a| a != station) {
+    rays
+      .entry(Slope::from(station, asteroid))
+      .or_default()
+      .push(asteroid);
+  }
+
+  let rays: Vec<_> = rays
+    .into_values()
+    .map(|mut ray| {
+      ray.sort_by_key(|a| a.manhattan(station));
+      ray.into()
     })
-    .collect_vec();
+    .collect();
 
-  res.sort_by(|a, b| a.partial_cmp(b).unwrap());
+  Vaporization {
+    ray: rays.len(),
+    rays,
+    rotation: 0,
+  }
+}
 
-  res
+fn asteroids(input: &str) -> Result<Vec<Coordinate>, parse::Error> {
+  let asteroids = parse_input(input)?;
+
+  if asteroids.is_empty() {
+    return Err(parse::Error::new("no asteroids on the map"));
+  }
+
+  Ok(asteroids)
 }
 
-pub fn solve(input: &str) {
-  let asteroids = parse_input(input);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let (count, _) = solve_01(&asteroids(input)?);
 
-  let (part_01, location) = solve_01(&asteroids);
-  dbg!(part_01);
+  Ok(count.to_string())
+}
 
-  let res = solve_02(location, &asteroids);
-  let (_, part_02) = res[199];
-  dbg!(part_02);
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let asteroids = asteroids(input)?;
+  let (_, location) = solve_01(&asteroids);
+
+  let (_, Point { x, y }) = vaporize(location, &asteroids)
+    .nth(199)
+    .ok_or_else(|| parse::Error::new("fewer than 200 asteroids to vaporize"))?;
+
+  Ok((x * 100 + y).to_string())
 }
 
 #[cfg(test)]
@@ -148,12 +293,27 @@ mod tests {
        #####
        ....#
        ...##",
-    );
+    )
+    .unwrap();
 
     let (count, coord) = solve_01(&asteroids);
 
     assert_eq!(count, 8);
-    assert_eq!(coord, (3, 4));
+    assert_eq!(coord, Point::new(3, 4));
+
+    // a blank line is still a row.
+    assert_eq!(
+      parse_input("#.\n\n.#").unwrap(),
+      vec![Point::new(0, 0), Point::new(1, 2)]
+    );
+    assert_eq!(
+      super::part_one("...\n...").unwrap_err().to_string(),
+      "no asteroids on the map"
+    );
+    assert_eq!(
+      super::part_two("#.#").unwrap_err().to_string(),
+      "fewer than 200 asteroids to vaporize"
+    );
   }
 
   #[test]
@@ -169,12 +329,13 @@ mod tests {
        .##.#..###
        ##...#..#.
        .#....####",
-    );
+    )
+    .unwrap();
 
     let (count, coord) = solve_01(&asteroids);
 
     assert_eq!(count, 33);
-    assert_eq!(coord, (5, 8));
+    assert_eq!(coord, Point::new(5, 8));
   }
 
   #[test]
@@ -200,18 +361,95 @@ mod tests {
        .#.#.###########.###
        #.#.#.#####.####.###
        ###.##.####.##.#..##",
-    );
+    )
+    .unwrap();
 
     let (count, coord) = solve_01(&asteroids);
 
     assert_eq!(count, 210);
-    assert_eq!(coord, (11, 13));
+    assert_eq!(coord, Point::new(11, 13));
 
-    let res = solve_02(coord, &asteroids);
-    res
+    let mut order = vaporize(coord, &asteroids);
+    assert_eq!(order.next(), Some((1, Point::new(11, 12))));
+    assert_eq!(order.nth(198), Some((1, Point::new(8, 2))));
+    assert_eq!(order.last(), Some((12, Point::new(11, 1))));
+  }
+
+  #[test]
+  fn clockwise() {
+    let station = Point::new(0, 0);
+    let around = [
+      (0, -1),
+      (1, -2),
+      (1, -1),
+      (1, 0),
+      (1, 1),
+      (0, 1),
+      (-1, 1),
+      (-1, 0),
+      (-1, -1),
+    ];
+    let slopes: Vec<_> = around
       .iter()
-      .enumerate()
-      .for_each(|(i, c)| println!("{}, {:?}", i, c));
-    assert_eq!(res[199].1, (8, 2));
+      .map(|&(x, y)| Slope::from(station, Point::new(x, y)))
+      .collect();
+
+    assert!(slopes.windows(2).all(|w| w[0] < w[1]));
+    assert!(slopes[8] < Slope::from(station, station));
+
+    // far enough out that f32 angles would tie.
+    let (a, b) = (Point::new(1, -16_777_216), Point::new(1, -16_777_215));
+    assert!(Slope::from(station, a) < Slope::from(station, b));
+  }
+
+  #[test]
+  fn rotations() {
+    let asteroids = parse_input("#.#\n...\n##.").unwrap();
+    let order: Vec<_> = vaporize(Point::new(0, 2), &asteroids).collect();
+
+    assert_eq!(
+      order,
+      vec![
+        (1, Point::new(0, 0)),
+        (1, Point::new(2, 0)),
+        (1, Point::new(1, 2)),
+      ]
+    );
+    assert_eq!(vaporize(Point::new(0, 0), &[Point::new(0, 0)]).next(), None);
+  }
+
+  #[test]
+  fn heatmaps() {
+    let asteroids = parse_input(
+      ".#..#
+       .....
+       #####
+       ....#
+       ...##",
+    )
+    .unwrap();
+
+    let counts = visible_counts(&asteroids, 3);
+    assert_eq!(counts, visible_counts(&asteroids, 1));
+
+    let grid = heatmap(&asteroids, &counts);
+    assert_eq!(
+      render_heatmap(&grid),
+      ". 7 . . 7\n. . . . .\n6 7 7 7 5\n. . . . 7\n. . . 8 7"
+    );
+
+    let palette = heat_palette(&grid);
+    assert_eq!(palette(&Some(8)), export::WHITE);
+    assert_eq!(palette(&None), export::BLACK);
+  }
+
+  #[test]
+  fn sees_and_hides() {
+    let asteroids = parse_input("#.#.#\n.....\n..#..").unwrap();
+    let lines = sightlines(Point::new(0, 0), &asteroids);
+
+    assert_eq!(lines.visible, vec![Point::new(2, 0), Point::new(2, 2)]);
+    assert_eq!(lines.blocked, vec![(Point::new(4, 0), Point::new(2, 0))]);
+    assert_eq!(visible_counts(&asteroids, 2), vec![2, 3, 2, 3]);
   }
 }
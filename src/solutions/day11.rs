@@ -1,190 +1,294 @@
+use crate::export::{Rgb, BLACK, WHITE};
+use crate::frames::Recorder;
+use crate::grid::{DenseGrid, Direction, Point, SparseGrid, Turn};
 use crate::intcode::*;
-use image::ImageBuffer;
+use crate::ocr;
+use crate::parse;
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::thread;
 
-type Coordinate = (isize, isize);
+type Coordinate = Point<isize>;
+
+const ROBOT: Rgb = [255, 64, 64];
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Color {
+pub enum Color {
   Black,
   White,
 }
 
-impl Into<isize> for Color {
-  fn into(self) -> isize {
-    match self {
+impl From<Color> for isize {
+  fn from(color: Color) -> isize {
+    match color {
       Color::Black => 0,
       Color::White => 1,
     }
   }
 }
 
-impl From<isize> for Color {
-  fn from(n: isize) -> Color {
+impl TryFrom<isize> for Color {
+  type Error = parse::Error;
+
+  fn try_from(n: isize) -> Result<Color, parse::Error> {
     match n {
-      0 => Color::Black,
-      1 => Color::White,
-      _ => unreachable!(),
+      0 => Ok(Color::Black),
+      1 => Ok(Color::White),
+      _ => Err(parse::Error::new(format!(
+        "the robot painted {}, expected 0 or 1",
+        n
+      ))),
     }
   }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-enum Turn {
-  Left,
-  Right,
-}
-
-impl From<isize> for Turn {
-  fn from(n: isize) -> Turn {
-    match n {
-      0 => Turn::Left,
-      1 => Turn::Right,
-      _ => unreachable!(),
-    }
+fn parse_turn(n: isize) -> Result<Turn, parse::Error> {
+  match n {
+    0 => Ok(Turn::Left),
+    1 => Ok(Turn::Right),
+    _ => Err(parse::Error::new(format!(
+      "the robot turned {}, expected 0 or 1",
+      n
+    ))),
   }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Direction {
-  Up,
-  Left,
-  Down,
-  Right,
+pub struct Move {
+  pub from: Coordinate,
+  pub painted: Color,
+  pub turn: Turn,
+  pub to: Coordinate,
 }
 
-impl Direction {
-  fn turn(self, turn: Turn) -> Direction {
-    match self {
-      Self::Up => match turn {
-        Turn::Left => Self::Left,
-        Turn::Right => Self::Right,
-      },
-      Self::Down => match turn {
-        Turn::Left => Self::Right,
-        Turn::Right => Self::Left,
-      },
-      Self::Left => match turn {
-        Turn::Left => Self::Down,
-        Turn::Right => Self::Up,
-      },
-      Self::Right => match turn {
-        Turn::Left => Self::Up,
-        Turn::Right => Self::Down,
-      },
+// drives any intcode program that reads the panel under it and answers with a color and a turn.
+#[derive(Debug, Clone)]
+pub struct PaintingRobot {
+  // the color of the panel the robot starts on, every other one starts black.
+  pub start: Color,
+  // moves before the robot stops, its program is cancelled wherever it got to.
+  pub limit: Option<usize>,
+  history: Vec<Move>,
+}
+
+impl PaintingRobot {
+  pub fn new(start: Color) -> PaintingRobot {
+    PaintingRobot {
+      start,
+      limit: None,
+      history: vec![],
     }
   }
-}
 
-trait Motion {
-  fn go(self, dir: Direction) -> Self;
-}
+  // every move of the last run, in order.
+  pub fn history(&self) -> &[Move] {
+    &self.history
+  }
 
-impl Motion for Coordinate {
-  fn go(self, dir: Direction) -> Self {
-    let (x, y) = self;
-    match dir {
-      Direction::Up => (x, y + 1),
-      Direction::Down => (x, y - 1),
-      Direction::Left => (x - 1, y),
-      Direction::Right => (x + 1, y),
-    }
+  pub fn paint(&mut self, program: &str) -> Result<SparseGrid<Color>, parse::Error> {
+    self.paint_with(program, |_, _| ())
   }
-}
 
-fn solve_01(input: &str) -> usize {
-  use std::thread;
+  // calls `each` with the panels and the robot's position after every move.
+  pub fn paint_with<F: FnMut(&SparseGrid<Color>, Coordinate)>(
+    &mut self,
+    program: &str,
+    mut each: F,
+  ) -> Result<SparseGrid<Color>, parse::Error> {
+    let mut vm = VM::try_from(program)?;
+
+    let output = vm.output.clone();
+    let input = vm.input.clone();
+    let cancel = vm.canceller();
+    let running = thread::spawn(move || vm.run());
+
+    let mut map = SparseGrid::new();
+    let mut loc = Coordinate::origin();
+    let mut facing = Direction::Up;
+    if self.start == Color::White {
+      map.insert(loc, self.start);
+    }
 
-  let mut vm = VM::from(input);
+    let panel = |map: &SparseGrid<Color>, loc| map.get(loc).copied().map_or(0, Color::into);
+
+    self.history.clear();
+    input
+      .send(panel(&map, loc))
+      .expect("initial send successful");
+
+    let mut failed = None;
+    for (color, turn) in output.iter().tuples() {
+      if self.limit == Some(self.history.len()) {
+        break;
+      }
+
+      let (painted, turn) = match (Color::try_from(color), parse_turn(turn)) {
+        (Ok(painted), Ok(turn)) => (painted, turn),
+        (Err(e), _) | (_, Err(e)) => {
+          failed = Some(e);
+          break;
+        }
+      };
+      let from = loc;
+
+      map.insert(loc, painted);
+      facing = facing.turn(turn);
+      loc = loc.step(facing);
+
+      self.history.push(Move {
+        from,
+        painted,
+        turn,
+        to: loc,
+      });
+      each(&map, loc);
+
+      input.send(panel(&map, loc)).unwrap_or_default();
+    }
 
-  let output = vm.output.clone();
-  let input = vm.input.clone();
+    // a program that never halts would keep going, so stop it rather than wait.
+    cancel.cancel();
+    running.join().expect("robot program stopped");
 
-  let mut map = HashMap::new();
-  let mut loc = (0, 0);
-  let mut facing = Direction::Up;
-  thread::spawn(move || vm.run());
+    match failed {
+      Some(e) => Err(e),
+      None => Ok(map),
+    }
+  }
+}
 
-  input
-    .send(map.get(&loc).copied().map(Color::into).unwrap_or(0))
-    .expect("initial send successful");
+// white panels, with the robot's up as the first row.
+fn lit(map: &SparseGrid<Color>) -> DenseGrid<bool> {
+  let white: SparseGrid<_> = map
+    .iter()
+    .filter(|(_, &v)| v == Color::White)
+    .map(|(p, &v)| (p, v))
+    .collect();
 
-  output.iter().tuples().for_each(|(color, turn)| {
-    let color = Color::from(color);
-    let turn = Turn::from(turn);
+  let mut picture = white.to_dense(Color::Black);
+  picture.flip_vertical();
 
-    map.insert(loc, color);
-    facing = facing.turn(turn);
-    loc = loc.go(facing);
-    input.send(map.get(&loc).copied().map(Color::into).unwrap_or(0));
-  });
+  picture.map(|&c| c == Color::White)
+}
 
-  map.len()
+pub fn picture(input: &str) -> Result<DenseGrid<bool>, parse::Error> {
+  Ok(lit(&PaintingRobot::new(Color::White).paint(input)?))
 }
 
-fn solve_02(input: &str) {
-  use std::thread;
+// the part two walk, with y flipped so the letters come out upright.
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), parse::Error> {
+  let flip = |p: Coordinate| Point::new(p.x, -p.y);
 
-  let mut vm = VM::from(input);
+  PaintingRobot::new(Color::White).paint_with(input, |map, robot| {
+    let panels = map.iter().map(|(p, &c)| {
+      let color = match c {
+        Color::Black => BLACK,
+        Color::White => WHITE,
+      };
 
-  let output = vm.output.clone();
-  let input = vm.input.clone();
+      (flip(p), color)
+    });
 
-  let mut map = HashMap::new();
-  let mut loc = (0, 0);
-  map.insert(loc, Color::White);
-  let mut facing = Direction::Up;
-  thread::spawn(move || vm.run());
+    recorder.points(panels.chain(std::iter::once((flip(robot), ROBOT))));
+  })?;
 
-  input
-    .send(map.get(&loc).copied().map(Color::into).unwrap_or(0))
-    .expect("initial send successful");
+  Ok(())
+}
 
-  output.iter().tuples().for_each(|(color, turn)| {
-    let color = Color::from(color);
-    let turn = Turn::from(turn);
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let painted = PaintingRobot::new(Color::Black).paint(input)?;
 
-    map.insert(loc, color);
-    facing = facing.turn(turn);
-    loc = loc.go(facing);
-    input.send(map.get(&loc).copied().map(Color::into).unwrap_or(0));
-  });
+  Ok(painted.len().to_string())
+}
 
-  let white_pixels = map
-    .iter()
-    .filter(|(_, &v)| v == Color::White)
-    .map(|(k, _)| k)
-    .collect_vec();
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let lit = picture(input)?;
 
-  let (min_x, _) = white_pixels.iter().min_by_key(|(x, _)| x).unwrap();
-  let (_, min_y) = white_pixels.iter().min_by_key(|(_, y)| y).unwrap();
+  Ok(ocr::read(&lit).unwrap_or_else(|| lit.render(|&p| if p { '▒' } else { ' ' })))
+}
 
-  let white_pixels = white_pixels
-    .iter()
-    .map(|(x, y)| (x - min_x, y - min_y))
-    .collect_vec();
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-  let (max_x, _) = *white_pixels.iter().max_by_key(|(x, _)| x).unwrap();
-  let (_, max_y) = *white_pixels.iter().max_by_key(|(_, y)| y).unwrap();
+  // reads a panel, then answers with the next color and turn from the puzzle's example.
+  fn bot() -> String {
+    let moves = [(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)];
 
-  let img = ImageBuffer::from_fn((max_x + 1) as u32, (max_y + 1) as u32, |x, y| {
-    let x = x as isize;
-    let y = y as isize;
+    moves
+      .iter()
+      .map(|(color, turn)| format!("3,100,104,{},104,{},", color, turn))
+      .collect::<String>()
+      + "99"
+  }
 
-    if white_pixels.contains(&(x, y)) {
-      image::Luma([255u8])
-    } else {
-      image::Luma([0u8])
-    }
-  });
+  #[test]
+  fn paints() {
+    let mut robot = PaintingRobot::new(Color::Black);
+    let map = robot.paint(&bot()).unwrap();
+
+    assert_eq!(map.len(), 6);
+    assert_eq!(robot.history().len(), 7);
+    assert_eq!(
+      robot.history()[0],
+      Move {
+        from: Point::new(0, 0),
+        painted: Color::White,
+        turn: Turn::Left,
+        to: Point::new(-1, 0),
+      }
+    );
+    assert_eq!(robot.history()[6].to, Point::new(0, 1));
+    assert_eq!(
+      lit(&map).render(|&p| if p { '#' } else { '.' }),
+      "..#\n..#\n##."
+    );
+  }
 
-  image::imageops::flip_vertical(&img)
-    .save("./out.png")
-    .expect("able to write answer");
-}
+  #[test]
+  fn stops_at_the_limit() {
+    let mut robot = PaintingRobot {
+      limit: Some(2),
+      ..PaintingRobot::new(Color::White)
+    };
+    let map = robot.paint(&bot()).unwrap();
+
+    assert_eq!(robot.history().len(), 2);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(Point::new(-1, 0)), Some(&Color::Black));
+  }
 
-pub fn solve(input: &str) {
-  dbg!(solve_01(input));
-  dbg!(solve_02(input));
+  #[test]
+  fn stops_bots_that_never_halt() {
+    // paints white and turns left forever, reading a panel each time or not at all.
+    for &looping in &["3,100,104,1,104,0,1105,1,0", "104,1,104,0,1105,1,0"] {
+      let mut robot = PaintingRobot {
+        limit: Some(5),
+        ..PaintingRobot::new(Color::Black)
+      };
+      let map = robot.paint(looping).unwrap();
+
+      assert_eq!(robot.history().len(), 5);
+      assert_eq!(map.len(), 4);
+    }
+  }
+
+  #[test]
+  fn bad_moves() {
+    let err = |program| {
+      PaintingRobot::new(Color::Black)
+        .paint(program)
+        .unwrap_err()
+        .to_string()
+    };
+
+    assert_eq!(
+      err("3,100,104,2,104,0,99"),
+      "the robot painted 2, expected 0 or 1"
+    );
+    assert_eq!(
+      err("3,100,104,1,104,-1,99"),
+      "the robot turned -1, expected 0 or 1"
+    );
+  }
 }
@@ -1,211 +1,171 @@
-use itertools::Itertools;
+use crate::export::Rgb;
+use crate::frames::Recorder;
+use crate::grid::Point;
+use crate::nbody::{Axis, System};
+use crate::parse::{self, Token};
 use regex::*;
-use std::collections::HashSet;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Vector {
-  x: i32,
-  y: i32,
-  z: i32,
+use std::sync::OnceLock;
+
+// `<x=1, y=-2, z=3>`, with any names and any number of coordinates.
+fn vector(token: Token) -> Result<Vec<i64>, parse::Error> {
+  let inside = token
+    .text
+    .strip_prefix('<')
+    .and_then(|t| t.strip_suffix('>'))
+    .ok_or_else(|| {
+      token.error(format!(
+        "expected `<x=_, y=_, z=_>`, found `{}`",
+        token.text
+      ))
+    })?;
+
+  token
+    .slice(1..1 + inside.len())
+    .split(',')
+    .map(|coordinate| coordinate.split_once('=')?.1.parse())
+    .collect()
 }
 
-impl Vector {
-  fn energy(self) -> i32 {
-    self.x.abs() + self.y.abs() + self.z.abs()
-  }
-
-  fn diff(self, other: Vector) -> (i32, i32, i32) {
-    use std::cmp::Ordering;
-
-    let x = match self.x.cmp(&other.x) {
-      Ordering::Equal => 0,
-      Ordering::Less => 1,
-      Ordering::Greater => -1,
-    };
-
-    let y = match self.y.cmp(&other.y) {
-      Ordering::Equal => 0,
-      Ordering::Less => 1,
-      Ordering::Greater => -1,
-    };
-
-    let z = match self.z.cmp(&other.z) {
-      Ordering::Equal => 0,
-      Ordering::Less => 1,
-      Ordering::Greater => -1,
-    };
-
-    (x, y, z)
-  }
+// one moon per line, either as `<x=_, y=_, z=_>` at rest or `pos=<..>, vel=<..>`,
+// and an optional `steps=_` line for part one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Scenario {
+  pub system: System,
+  pub steps: Option<usize>,
 }
 
-impl std::ops::Add<Vector> for Vector {
-  type Output = Vector;
-  fn add(self, other: Vector) -> Vector {
-    Vector {
-      x: self.x + other.x,
-      y: self.y + other.y,
-      z: self.z + other.z,
-    }
-  }
-}
+impl Scenario {
+  pub fn parse(input: &str) -> Result<Scenario, parse::Error> {
+    static MOON: OnceLock<Regex> = OnceLock::new();
+    let moon =
+      MOON.get_or_init(|| Regex::new(r"^(?:pos=(<[^>]*>),\s*vel=(<[^>]*>)|(<[^>]*>))$").unwrap());
+
+    let mut steps = None;
+    let mut positions: Vec<Vec<i64>> = vec![];
+    let mut velocities: Vec<Vec<i64>> = vec![];
+
+    for line in parse::lines(input) {
+      if let Some(count) = line.text.strip_prefix("steps=") {
+        if steps.is_some() {
+          return Err(line.error("steps are given twice"));
+        }
+
+        steps = Some(
+          line
+            .slice(line.text.len() - count.len()..line.text.len())
+            .parse()?,
+        );
+        continue;
+      }
+
+      let caps = moon.captures(line.text).ok_or_else(|| {
+        line.error(format!(
+          "expected `<x=_, y=_, z=_>` or `pos=<..>, vel=<..>`, found `{}`",
+          line.text
+        ))
+      })?;
+      let part = |i| caps.get(i).map(|m| line.slice(m.start()..m.end()));
+
+      let (p, v) = match (part(1), part(2), part(3)) {
+        (Some(p), Some(v), _) => {
+          let (p, v) = (vector(p)?, vector(v)?);
+
+          if p.len() != v.len() {
+            return Err(line.error(format!(
+              "{} coordinates with {} velocities",
+              p.len(),
+              v.len()
+            )));
+          }
 
-impl From<&str> for Vector {
-  fn from(input: &str) -> Vector {
-    let re = Regex::new(r"<x=(.*), y=(.*), z=(.*)>").unwrap();
+          (p, v)
+        }
+        (_, _, Some(p)) => {
+          let p = vector(p)?;
+          let v = vec![0; p.len()];
+
+          (p, v)
+        }
+        _ => unreachable!(),
+      };
+
+      if let Some(first) = positions.first().filter(|first| first.len() != p.len()) {
+        return Err(line.error(format!(
+          "{} coordinates, but the first moon has {}",
+          p.len(),
+          first.len()
+        )));
+      }
+
+      positions.push(p);
+      velocities.push(v);
+    }
 
-    let caps = re.captures(input).unwrap();
+    if positions.is_empty() {
+      return Err(parse::Error::new("expected at least one moon"));
+    }
 
-    let x = caps.get(1).map_or("", |m| m.as_str()).parse().unwrap();
-    let y = caps.get(2).map_or("", |m| m.as_str()).parse().unwrap();
-    let z = caps.get(3).map_or("", |m| m.as_str()).parse().unwrap();
+    let system = System::with_velocities(&positions, &velocities).expect("checked every moon");
 
-    Vector { x, y, z }
+    Ok(Scenario { system, steps })
   }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Moon {
-  p: Vector,
-  v: Vector,
-}
-
-impl Moon {
-  fn energy(&self) -> i32 {
-    self.p.energy() * self.v.energy()
+// total energy after `steps` steps.
+fn solve_01(system: System, steps: usize) -> i64 {
+  match steps {
+    0 => system.energy(),
+    _ => system.energies().nth(steps - 1).unwrap(),
   }
 }
 
-#[derive(Clone, Debug)]
-struct Simulation {
-  moons: Vec<Moon>,
-}
-
-impl Simulation {
-  fn new(moons: &[Moon]) -> Self {
-    Self {
-      moons: moons.to_vec(),
-    }
-  }
+fn solve_02(system: &System) -> Option<u64> {
+  system.period()
 }
 
-impl<'a> Iterator for Simulation {
-  type Item = Vec<Moon>;
-
-  fn next(&mut self) -> Option<Vec<Moon>> {
-    use std::cmp::Ordering;
-    let new_moons = self
-      .moons
-      .iter()
-      .map(|moon| {
-        let v = self.moons.iter().fold(moon.v, |acc, next| {
-          let (dx, dy, dz) = moon.p.diff(next.p);
-
-          Vector {
-            x: acc.x + dx,
-            y: acc.y + dy,
-            z: acc.z + dz,
-          }
-        });
-        Moon { p: moon.p, v }
-      })
-      .map(|moon| Moon {
-        p: moon.p + moon.v,
-        v: moon.v,
-      })
-      .collect();
-
-    self.moons = new_moons;
-    Some(self.moons.clone())
-  }
+fn parse(input: &str) -> Result<System, parse::Error> {
+  Ok(Scenario::parse(input)?.system)
 }
 
-fn solve_01(moons: &[Moon], steps: usize) -> i32 {
-  let mut sim = Simulation::new(moons);
+// the moons seen from above, one color each, over part one's steps.
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), parse::Error> {
+  const COLORS: [Rgb; 4] = [[255, 64, 64], [64, 255, 64], [64, 128, 255], [255, 255, 64]];
 
-  let state = sim.nth(steps).unwrap();
+  let system = parse(input)?;
+  let start = std::iter::once(system.clone());
 
-  state.iter().map(|m| m.energy()).sum()
-}
-
-fn gcd(mut m: usize, mut n: usize) -> usize {
-  while m != 0 {
-    let old_m = m;
-    m = n % m;
-    n = old_m;
+  for state in start.chain(system.history().take(1000)) {
+    let axis = |d| {
+      state
+        .axes()
+        .get(d)
+        .map_or(&[][..], |a: &Axis| &a.positions[..])
+    };
+    let (xs, ys) = (axis(0), axis(1));
+
+    recorder.points(
+      xs.iter()
+        .zip(ys.iter().chain(std::iter::repeat(&0)))
+        .zip(COLORS.iter().cycle())
+        .map(|((&x, &y), &color)| (Point::new(x as isize, y as isize), color)),
+    );
   }
 
-  n
-}
-
-fn lcm(a: usize, b: usize) -> usize {
-  a * b / gcd(a, b)
+  Ok(())
 }
 
-fn solve_02(moons: &[Moon]) -> usize {
-  let sim = Simulation::new(moons);
-
-  let mut set = HashSet::new();
-
-  for state in sim {
-    let i = state.iter().map(|m| (m.p.x, m.v.x)).collect_vec();
-
-    if set.contains(&i) {
-      break;
-    }
-
-    set.insert(i);
-  }
-
-  let x = set.len();
-
-  let sim = Simulation::new(moons);
-
-  set.clear();
-
-  for state in sim {
-    let i = state.iter().map(|m| (m.p.y, m.v.y)).collect_vec();
-
-    if set.contains(&i) {
-      break;
-    }
-    set.insert(i);
-  }
-
-  let y = set.len();
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  let scenario = Scenario::parse(input)?;
 
-  let sim = Simulation::new(moons);
-
-  set.clear();
-
-  for state in sim {
-    let i = state.iter().map(|m| (m.p.z, m.v.z)).collect_vec();
-
-    if set.contains(&i) {
-      break;
-    }
-    set.insert(i);
-  }
-
-  let z = set.len();
-  dbg!(x, y, z);
-  lcm(x, lcm(y, z))
+  Ok(solve_01(scenario.system, scenario.steps.unwrap_or(1000)).to_string())
 }
 
-pub fn solve(input: &str) {
-  let moons = input
-    .lines()
-    .map(|line| {
-      let p = line.trim().into();
-      let v = Vector { x: 0, y: 0, z: 0 };
-
-      Moon { p, v }
-    })
-    .collect_vec();
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  let period = solve_02(&parse(input)?).ok_or_else(|| {
+    parse::Error::new("the moons never repeat, or take more than a u64 of steps to")
+  })?;
 
-  dbg!(solve_01(&moons, 999));
-
-  dbg!(solve_02(&moons));
+  Ok(period.to_string())
 }
 
 #[cfg(test)]
@@ -219,17 +179,9 @@ mod tests {
     <x=2, y=-7, z=3>
     <x=9, y=-8, z=-3>";
 
-    let moons = input
-      .lines()
-      .map(|line| {
-        let p = line.trim().into();
-        let v = Vector { x: 0, y: 0, z: 0 };
-
-        Moon { p, v }
-      })
-      .collect_vec();
+    let moons = parse(input).unwrap();
 
-    assert_eq!(solve_01(&moons, 99), 1940);
+    assert_eq!(solve_01(moons, 100), 1940);
   }
 
   #[test]
@@ -239,16 +191,66 @@ mod tests {
     <x=2, y=-7, z=3>
     <x=9, y=-8, z=-3>";
 
-    let moons = input
-      .lines()
-      .map(|line| {
-        let p = line.trim().into();
-        let v = Vector { x: 0, y: 0, z: 0 };
+    let moons = parse(input).unwrap();
 
-        Moon { p, v }
-      })
-      .collect_vec();
+    assert_eq!(solve_02(&moons), Some(4_686_774_924));
+  }
 
-    assert_eq!(solve_02(&moons), 4_686_774_924);
+  #[test]
+  fn bad_moons() {
+    let err = |input| parse(input).unwrap_err().to_string();
+
+    assert_eq!(
+      err("<x=1, y=2, z=3>\n  <x=1, y=b, z=3>"),
+      "line 2, column 11: `b`: invalid digit found in string"
+    );
+    assert_eq!(
+      err("<x=1, y=2, z=3>\n<x=1, y=2>"),
+      "line 2, column 1: 2 coordinates, but the first moon has 3"
+    );
+    assert_eq!(
+      err("x=1, y=2"),
+      "line 1, column 1: expected `<x=_, y=_, z=_>` or `pos=<..>, vel=<..>`, found `x=1, y=2`"
+    );
+    assert_eq!(
+      err("pos=<x=1, y=2>, vel=<x=0>"),
+      "line 1, column 1: 2 coordinates with 1 velocities"
+    );
+    assert_eq!(err("<x=1, y 2>"), "line 1, column 7: expected `=` in `y 2`");
+    assert_eq!(
+      err("steps=ten"),
+      "line 1, column 7: `ten`: invalid digit found in string"
+    );
+    assert_eq!(err(""), "expected at least one moon");
+    assert_eq!(err("steps=10\n"), "expected at least one moon");
+  }
+
+  #[test]
+  fn scenarios() {
+    let scenario = Scenario::parse(
+      "steps=10
+      pos=<x= 2, y= 1, z=-3>, vel=<x=-3, y=-2, z= 1>
+      pos=<x= 1, y=-8, z= 0>, vel=<x=-1, y= 1, z= 3>
+      pos=<x= 3, y=-6, z= 1>, vel=<x= 3, y= 2, z=-3>
+      pos=<x= 2, y= 0, z= 4>, vel=<x= 1, y=-1, z=-1>",
+    )
+    .unwrap();
+
+    assert_eq!(scenario.steps, Some(10));
+    assert_eq!(scenario.system.energy(), 179);
+    assert_eq!(scenario.system.velocity(3), vec![1, -1, -1]);
+
+    // the example, ten steps in, is part of the same loop.
+    assert_eq!(solve_02(&scenario.system), Some(2772));
+
+    let flat = "steps=3\n<a=0, b=5>\n<a=3, b=5>";
+    assert_eq!(super::part_one(flat).unwrap(), "15");
+    assert_eq!(super::part_two(flat).unwrap(), "8");
+
+    let drifting = "pos=<x=0>, vel=<x=1>";
+    assert_eq!(
+      super::part_two(drifting).unwrap_err().message,
+      "the moons never repeat, or take more than a u64 of steps to"
+    );
   }
 }
@@ -1,6 +1,10 @@
+use crate::export::{BLACK, WHITE};
+use crate::frames::Recorder;
+use crate::grid::{Point, SparseGrid};
 use crate::intcode::*;
+use crate::parse;
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::thread;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -25,58 +29,154 @@ impl From<isize> for Tile {
   }
 }
 
-fn solve_01(input: &str) -> usize {
-  let mut vm = VM::from(input);
+fn solve_01(input: &str) -> Result<usize, parse::Error> {
+  let mut vm = VM::try_from(input)?;
 
   let output = vm.output.clone();
 
   thread::spawn(move || vm.run());
 
-  let mut m = HashMap::new();
+  let mut m = SparseGrid::new();
 
   output.iter().tuples().for_each(|(a, b, c)| {
-    m.insert((a, b), Tile::from(c));
+    m.insert(Point::new(a, b), Tile::from(c));
   });
 
-  m.values().filter(|&c| *c == Tile::Block).count()
+  Ok(m.values().filter(|&c| *c == Tile::Block).count())
 }
 
-fn solve_02(input: &str) -> isize {
-  let mut vm = VM::from(input);
+#[derive(Debug, Clone, Default)]
+pub struct Screen {
+  tiles: SparseGrid<Tile>,
+  score: isize,
+  ball: Option<Point<isize>>,
+  paddle: Option<Point<isize>>,
+}
+
+impl Screen {
+  pub fn tiles(&self) -> &SparseGrid<Tile> {
+    &self.tiles
+  }
+
+  pub fn score(&self) -> isize {
+    self.score
+  }
+
+  pub fn blocks(&self) -> usize {
+    self.tiles.values().filter(|&t| *t == Tile::Block).count()
+  }
+
+  pub fn render(&self) -> String {
+    self.tiles.render(|tile| match tile {
+      None | Some(Tile::Empty) => ' ',
+      Some(Tile::Wall) => '█',
+      Some(Tile::Block) => '▒',
+      Some(Tile::Paddle) => '▀',
+      Some(Tile::Ball) => '●',
+    })
+  }
+}
+
+// keeps the paddle under the ball.
+pub fn autopilot(screen: &Screen) -> isize {
+  match (screen.ball, screen.paddle) {
+    (Some(ball), Some(paddle)) => (ball.x - paddle.x).signum(),
+    _ => 0,
+  }
+}
+
+// plays until the game halts, asking `joystick` for a tilt whenever the ball has moved.
+pub fn play<F: FnMut(&Screen) -> isize>(
+  input: &str,
+  mut joystick: F,
+) -> Result<Screen, parse::Error> {
+  let mut vm = VM::try_from(input)?;
   vm.memory[0] = 2;
 
   let output = vm.output.clone();
   let input = vm.input.clone();
-  input.send(0);
+  input.send(0).expect("initial send successful");
   thread::spawn(move || vm.run());
 
-  let mut score = 0;
-  let mut ball = 0;
-  let mut paddle = -1;
+  let mut screen = Screen::default();
 
-  output.iter().tuples().for_each(|(x, _, t)| {
+  output.iter().tuples().for_each(|(x, y, t)| {
     if x == -1 {
-      score = t;
-      dbg!(score);
-    } else {
-      match Tile::from(t) {
-        Tile::Ball => {
-          ball = x;
-          if paddle > 0 {
-            let joystick = (ball - paddle).signum();
-            input.send(joystick);
-          }
-        }
-        Tile::Paddle => paddle = x,
-        _ => (),
-      };
+      screen.score = t;
+      return;
     }
+
+    let p = Point::new(x, y);
+    let tile = Tile::from(t);
+    screen.tiles.insert(p, tile);
+
+    match tile {
+      Tile::Ball => {
+        screen.ball = Some(p);
+        if screen.paddle.is_some() {
+          input.send(joystick(&screen)).unwrap_or_default();
+        }
+      }
+      Tile::Paddle => screen.paddle = Some(p),
+      _ => (),
+    };
   });
 
-  score
+  Ok(screen)
+}
+
+fn solve_02(input: &str) -> Result<isize, parse::Error> {
+  Ok(play(input, autopilot)?.score())
+}
+
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), parse::Error> {
+  play(input, |screen| {
+    recorder.sparse(screen.tiles(), |tile| match tile {
+      Tile::Empty => BLACK,
+      Tile::Wall => [128, 128, 128],
+      Tile::Block => [64, 128, 255],
+      Tile::Paddle => WHITE,
+      Tile::Ball => [255, 64, 64],
+    });
+
+    autopilot(screen)
+  })?;
+
+  Ok(())
+}
+
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_01(input)?.to_string())
 }
 
-pub fn solve(input: &str) {
-  dbg!(solve_01(input));
-  dbg!(solve_02(input));
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_02(input)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // draws a paddle and a ball, then shows the joystick it was given as the score.
+  const GAME: &str =
+    "1101,0,0,50,3,100,104,1,104,1,104,3,104,2,104,0,104,4,3,101,104,-1,104,0,4,101,99";
+
+  #[test]
+  fn headless() {
+    let screen = play(GAME, autopilot).unwrap();
+
+    assert_eq!(screen.score(), 1);
+    assert_eq!(screen.render(), " ●\n▀ ");
+
+    let mut frames = 0;
+    let screen = play(GAME, |_| {
+      frames += 1;
+      -1
+    })
+    .unwrap();
+
+    assert_eq!(frames, 1);
+    assert_eq!(screen.score(), -1);
+    assert_eq!(screen.blocks(), 0);
+  }
 }
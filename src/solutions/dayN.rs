@@ -1,22 +1,40 @@
-fn solve_01() {}
-fn solve_02() {}
+use crate::parse;
 
-pub fn solve(input: &str) {
-  dbg!(solve_01());
-  dbg!(solve_02());
+fn solve_01(_input: &str) -> usize {
+  0
+}
+
+fn solve_02(_input: &str) -> usize {
+  0
+}
+
+pub fn part_one(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_01(input).to_string())
+}
+
+pub fn part_two(input: &str) -> Result<String, parse::Error> {
+  Ok(solve_02(input).to_string())
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  // (example, answer) pairs from the puzzle text.
+  const EXAMPLES_01: &[(&str, usize)] = &[];
+  const EXAMPLES_02: &[(&str, usize)] = &[];
+
   #[test]
   fn part_one() {
-    unimplemented!();
+    for (example, answer) in EXAMPLES_01 {
+      assert_eq!(solve_01(example), *answer);
+    }
   }
 
   #[test]
   fn part_two() {
-    unimplemented!();
+    for (example, answer) in EXAMPLES_02 {
+      assert_eq!(solve_02(example), *answer);
+    }
   }
 }
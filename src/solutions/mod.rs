@@ -11,3 +11,16 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
+
+use crate::frames::Recorder;
+use crate::grid::DenseGrid;
+use crate::parse;
+
+// threads to split a search across, one when that can't be told.
+pub fn workers() -> usize {
+  std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+pub type Solver = fn(&str) -> Result<String, parse::Error>;
+pub type Picture = fn(&str) -> Result<DenseGrid<bool>, parse::Error>;
+pub type Recording = fn(&str, &mut Recorder) -> Result<(), parse::Error>;
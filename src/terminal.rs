@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+const HOME: &str = "\x1b[H";
+const CLEAR: &str = "\x1b[2J";
+const CLEAR_LINE: &str = "\x1b[K";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub fn start<W: Write>(out: &mut W) -> io::Result<()> {
+  write!(out, "{}{}", CLEAR, HIDE_CURSOR)?;
+  out.flush()
+}
+
+pub fn finish<W: Write>(out: &mut W) -> io::Result<()> {
+  writeln!(out, "{}", SHOW_CURSOR)?;
+  out.flush()
+}
+
+// draws over the last frame from the top left instead of scrolling.
+pub fn redraw<W: Write>(out: &mut W, frame: &str, status: &str) -> io::Result<()> {
+  write!(out, "{}", HOME)?;
+
+  for line in frame.lines().chain(std::iter::once(status)) {
+    write!(out, "{}{}\r\n", line, CLEAR_LINE)?;
+  }
+
+  out.flush()
+}
+
+// frames per second over the last `window` frames.
+#[derive(Debug, Clone)]
+pub struct FrameRate {
+  window: usize,
+  times: VecDeque<Instant>,
+}
+
+impl FrameRate {
+  pub fn new(window: usize) -> Self {
+    Self {
+      window: window.max(2),
+      times: VecDeque::new(),
+    }
+  }
+
+  pub fn tick(&mut self, now: Instant) -> f64 {
+    if self.times.len() == self.window {
+      self.times.pop_front();
+    }
+    self.times.push_back(now);
+
+    let elapsed = now.duration_since(self.times[0]).as_secs_f64();
+
+    if elapsed > 0. {
+      (self.times.len() - 1) as f64 / elapsed
+    } else {
+      0.
+    }
+  }
+}
+
+// the last arrow key pressed wins, left is -1, right 1 and anything else leaves it centered.
+pub fn joystick(keys: &[u8]) -> isize {
+  keys
+    .windows(3)
+    .rev()
+    .find_map(|key| match key {
+      b"\x1b[D" => Some(-1),
+      b"\x1b[C" => Some(1),
+      _ => None,
+    })
+    .unwrap_or(0)
+}
+
+// keys arrive unbuffered and unechoed, and reads give up after a tenth of a second.
+pub struct RawMode {
+  saved: String,
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+  let output = Command::new("stty")
+    .args(args)
+    .stdin(Stdio::inherit())
+    .output()?;
+
+  if !output.status.success() {
+    return Err(io::Error::other(
+      String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl RawMode {
+  pub fn enable() -> io::Result<RawMode> {
+    let saved = stty(&["-g"])?;
+    stty(&["-icanon", "-echo", "min", "0", "time", "1"])?;
+
+    Ok(RawMode { saved })
+  }
+
+  // 0 when no key came in time.
+  pub fn read_joystick(&self) -> isize {
+    let mut keys = [0; 16];
+    let read = io::stdin().read(&mut keys).unwrap_or(0);
+
+    joystick(&keys[..read])
+  }
+}
+
+impl Drop for RawMode {
+  fn drop(&mut self) {
+    stty(&[&self.saved]).unwrap_or_default();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+
+  #[test]
+  fn redraws_in_place() {
+    let mut out = vec![];
+    redraw(&mut out, "ab\ncd", "score: 3").unwrap();
+
+    assert_eq!(
+      String::from_utf8(out).unwrap(),
+      "\x1b[Hab\x1b[K\r\ncd\x1b[K\r\nscore: 3\x1b[K\r\n"
+    );
+  }
+
+  #[test]
+  fn frame_rate() {
+    let start = Instant::now();
+    let mut rate = FrameRate::new(3);
+
+    assert_eq!(rate.tick(start), 0.);
+    assert_eq!(rate.tick(start + Duration::from_millis(100)), 10.);
+    assert_eq!(rate.tick(start + Duration::from_millis(200)), 10.);
+    assert_eq!(rate.tick(start + Duration::from_millis(250)), 2. / 0.15);
+  }
+
+  #[test]
+  fn arrow_keys() {
+    assert_eq!(joystick(b""), 0);
+    assert_eq!(joystick(b"x"), 0);
+    assert_eq!(joystick(b"\x1b[D"), -1);
+    assert_eq!(joystick(b"\x1b[C"), 1);
+    assert_eq!(joystick(b"\x1b[C\x1b[A\x1b[D"), -1);
+  }
+}
@@ -0,0 +1,123 @@
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub struct Watcher {
+  input: PathBuf,
+  examples: PathBuf,
+  seen: Option<BTreeMap<PathBuf, Option<SystemTime>>>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+  fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Watcher {
+  pub fn new<P: Into<PathBuf>, Q: Into<PathBuf>>(input: P, examples: Q) -> Self {
+    Self {
+      input: input.into(),
+      examples: examples.into(),
+      seen: None,
+    }
+  }
+
+  pub fn input(&self) -> &Path {
+    &self.input
+  }
+
+  // the input first, then every `*.txt` in the examples directory in name order.
+  pub fn files(&self) -> Vec<PathBuf> {
+    let examples = fs::read_dir(&self.examples)
+      .map(|entries| {
+        entries
+          .flat_map(|entry| entry.ok())
+          .map(|entry| entry.path())
+          .filter(|path| path.extension() == Some("txt".as_ref()))
+          .sorted()
+          .collect_vec()
+      })
+      .unwrap_or_default();
+
+    std::iter::once(self.input.clone())
+      .filter(|path| path.exists())
+      .chain(examples)
+      .collect()
+  }
+
+  // true on the first poll, and whenever a file was added, removed or touched since the last one.
+  pub fn poll(&mut self) -> bool {
+    let now: BTreeMap<_, _> = self
+      .files()
+      .into_iter()
+      .map(|path| {
+        let time = modified(&path);
+        (path, time)
+      })
+      .collect();
+
+    let changed = self.seen.as_ref() != Some(&now);
+    self.seen = Some(now);
+
+    changed
+  }
+}
+
+pub fn side_by_side(before: &str, after: &str) -> String {
+  let before = before.lines().collect_vec();
+  let after = after.lines().collect_vec();
+  let width = before.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+  (0..before.len().max(after.len()))
+    .map(|i| {
+      let left = before.get(i).copied().unwrap_or("");
+      let right = after.get(i).copied().unwrap_or("");
+      let pad = width - left.chars().count();
+
+      format!("{}{} | {}", left, " ".repeat(pad), right)
+    })
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn columns() {
+    assert_eq!(side_by_side("135", "159"), "135 | 159");
+    assert_eq!(side_by_side("a\nbcd", "x"), "a   | x\nbcd | ");
+    assert_eq!(side_by_side("-", "▒ ▒\n ▒ "), "- | ▒ ▒\n  |  ▒ ");
+  }
+
+  #[test]
+  fn notices_changes() {
+    let dir = std::env::temp_dir().join(format!("advent-watch-{}", std::process::id()));
+    let examples = dir.join("examples");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&examples).unwrap();
+    fs::write(dir.join("input.txt"), "1").unwrap();
+
+    let mut watcher = Watcher::new(dir.join("input.txt"), &examples);
+
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+
+    fs::write(examples.join("one.txt"), "2").unwrap();
+    fs::write(examples.join("notes.md"), "").unwrap();
+
+    assert!(watcher.poll());
+    assert_eq!(
+      watcher.files(),
+      vec![dir.join("input.txt"), examples.join("one.txt")]
+    );
+
+    fs::remove_file(examples.join("one.txt")).unwrap();
+
+    assert!(watcher.poll());
+    assert_eq!(watcher.files(), vec![dir.join("input.txt")]);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,361 @@
+use crate::grid::{Direction, Point};
+use std::collections::{BTreeMap, HashMap};
+
+type Coordinate = Point<isize>;
+
+// a straight run of a wire, covering `end` but not `start`, which the run before it already covers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Segment {
+  pub start: Coordinate,
+  pub end: Coordinate,
+  // taken before `start`.
+  pub steps: isize,
+}
+
+impl Segment {
+  fn horizontal(&self) -> bool {
+    self.start.y == self.end.y
+  }
+
+  fn xs(&self) -> (isize, isize) {
+    (self.start.x.min(self.end.x), self.start.x.max(self.end.x))
+  }
+
+  fn ys(&self) -> (isize, isize) {
+    (self.start.y.min(self.end.y), self.start.y.max(self.end.y))
+  }
+
+  fn covers(&self, p: Coordinate) -> bool {
+    let ((x0, x1), (y0, y1)) = (self.xs(), self.ys());
+
+    p != self.start && x0 <= p.x && p.x <= x1 && y0 <= p.y && p.y <= y1
+  }
+
+  fn steps_to(&self, p: Coordinate) -> isize {
+    self.steps + p.manhattan(self.start)
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Wire {
+  segments: Vec<Segment>,
+}
+
+impl Wire {
+  // starts at the origin, runs of length 0 are dropped.
+  pub fn new<I: IntoIterator<Item = (Direction, usize)>>(route: I) -> Wire {
+    let mut at = Coordinate::origin();
+    let mut steps = 0;
+
+    let segments = route
+      .into_iter()
+      .filter(|&(_, len)| len > 0)
+      .map(|(dir, len)| {
+        let len = len as isize;
+        let segment = Segment {
+          start: at,
+          end: at + dir.delta() * len,
+          steps,
+        };
+
+        at = segment.end;
+        steps += len;
+
+        segment
+      })
+      .collect();
+
+    Wire { segments }
+  }
+
+  pub fn segments(&self) -> &[Segment] {
+    &self.segments
+  }
+}
+
+// a point other than the origin where at least two segments meet, from different wires or the same one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Crossing {
+  pub point: Coordinate,
+  // from the origin.
+  pub distance: isize,
+  // every wire through the point with its fewest steps there, ordered by wire.
+  pub wires: Vec<(usize, isize)>,
+}
+
+impl Crossing {
+  // a wire crossing only itself.
+  pub fn is_self(&self) -> bool {
+    self.wires.len() == 1
+  }
+
+  // every wire's steps added up.
+  pub fn steps(&self) -> isize {
+    self.wires.iter().map(|&(_, steps)| steps).sum()
+  }
+}
+
+// pairs of (wire, segment) meeting at a point, both covering it.
+type Meeting = (Coordinate, usize, usize);
+
+// sweeps left to right, keeping the horizontal segments under the sweep line ordered by y,
+// so every vertical segment only looks at the ones in its y range.
+fn perpendicular(segments: &[(usize, Segment)]) -> Vec<Meeting> {
+  const ADD: u8 = 0;
+  const QUERY: u8 = 1;
+  const REMOVE: u8 = 2;
+
+  let mut events = vec![];
+
+  for (i, (_, segment)) in segments.iter().enumerate() {
+    let (x0, x1) = segment.xs();
+
+    if segment.horizontal() {
+      events.push((x0, ADD, i));
+      events.push((x1, REMOVE, i));
+    } else {
+      events.push((x0, QUERY, i));
+    }
+  }
+
+  events.sort_unstable();
+
+  let mut active: BTreeMap<isize, Vec<usize>> = BTreeMap::new();
+  let mut meetings = vec![];
+
+  for (x, kind, i) in events {
+    let segment = segments[i].1;
+
+    match kind {
+      ADD => active.entry(segment.start.y).or_default().push(i),
+      REMOVE => {
+        let row = active.get_mut(&segment.start.y).unwrap();
+        row.retain(|&h| h != i);
+      }
+      _ => {
+        let (y0, y1) = segment.ys();
+
+        for (&y, row) in active.range(y0..=y1) {
+          meetings.extend(row.iter().map(|&h| (Point::new(x, y), h, i)));
+        }
+      }
+    }
+  }
+
+  meetings
+}
+
+// segments on the same line share every point where their ranges overlap.
+fn collinear(segments: &[(usize, Segment)]) -> Vec<Meeting> {
+  let mut lines: HashMap<(bool, isize), Vec<usize>> = HashMap::new();
+
+  for (i, (_, segment)) in segments.iter().enumerate() {
+    let key = if segment.horizontal() {
+      (true, segment.start.y)
+    } else {
+      (false, segment.start.x)
+    };
+
+    lines.entry(key).or_default().push(i);
+  }
+
+  let mut meetings = vec![];
+
+  for ((horizontal, at), line) in lines {
+    let range = |i: usize| {
+      let segment = segments[i].1;
+
+      if horizontal {
+        segment.xs()
+      } else {
+        segment.ys()
+      }
+    };
+
+    for (n, &a) in line.iter().enumerate() {
+      for &b in &line[n + 1..] {
+        let ((a0, a1), (b0, b1)) = (range(a), range(b));
+
+        for along in a0.max(b0)..=a1.min(b1) {
+          let p = if horizontal {
+            Point::new(along, at)
+          } else {
+            Point::new(at, along)
+          };
+
+          meetings.push((p, a, b));
+        }
+      }
+    }
+  }
+
+  meetings
+}
+
+// every crossing of any of the wires, ordered by point.
+pub fn crossings(wires: &[Wire]) -> Vec<Crossing> {
+  let segments: Vec<(usize, Segment)> = wires
+    .iter()
+    .enumerate()
+    .flat_map(|(w, wire)| wire.segments().iter().map(move |&s| (w, s)))
+    .collect();
+
+  let mut points: BTreeMap<Coordinate, BTreeMap<usize, isize>> = BTreeMap::new();
+
+  let meetings = perpendicular(&segments)
+    .into_iter()
+    .chain(collinear(&segments));
+
+  for (p, i, j) in meetings {
+    let ((wi, si), (wj, sj)) = (segments[i], segments[j]);
+
+    // a corner is shared by consecutive segments, but only the first one covers it,
+    // and wires coming back to where they all start don't count.
+    if p == Coordinate::origin() || !si.covers(p) || !sj.covers(p) {
+      continue;
+    }
+
+    // a wire coming back keeps the steps of its first visit.
+    let wires = points.entry(p).or_default();
+    for &(w, s) in &[(wi, si), (wj, sj)] {
+      let taken = s.steps_to(p);
+      let fewest = wires.entry(w).or_insert(taken);
+      *fewest = (*fewest).min(taken);
+    }
+  }
+
+  points
+    .into_iter()
+    .map(|(point, wires)| Crossing {
+      point,
+      distance: point.manhattan(Coordinate::origin()),
+      wires: wires.into_iter().collect(),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use Direction::*;
+
+  #[test]
+  fn segments() {
+    let wire = Wire::new(vec![(Right, 8), (Up, 0), (Up, 5), (Left, 5)]);
+
+    assert_eq!(
+      wire.segments(),
+      &[
+        Segment {
+          start: Point::new(0, 0),
+          end: Point::new(8, 0),
+          steps: 0
+        },
+        Segment {
+          start: Point::new(8, 0),
+          end: Point::new(8, 5),
+          steps: 8
+        },
+        Segment {
+          start: Point::new(8, 5),
+          end: Point::new(3, 5),
+          steps: 13
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn crosses() {
+    let a = Wire::new(vec![(Right, 8), (Up, 5), (Left, 5), (Down, 3)]);
+    let b = Wire::new(vec![(Up, 7), (Right, 6), (Down, 4), (Left, 4)]);
+
+    assert_eq!(
+      crossings(&[a, b]),
+      vec![
+        Crossing {
+          point: Point::new(3, 3),
+          distance: 6,
+          wires: vec![(0, 20), (1, 20)]
+        },
+        Crossing {
+          point: Point::new(6, 5),
+          distance: 11,
+          wires: vec![(0, 15), (1, 15)]
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn overlaps() {
+    let a = Wire::new(vec![(Right, 5)]);
+    let b = Wire::new(vec![(Up, 1), (Right, 2), (Down, 1), (Right, 2)]);
+
+    let crossings = crossings(&[a, b]);
+    let points: Vec<_> = crossings.iter().map(|c| c.point).collect();
+
+    assert_eq!(
+      points,
+      vec![Point::new(2, 0), Point::new(3, 0), Point::new(4, 0)]
+    );
+    assert_eq!(crossings[0].steps(), 2 + 4);
+
+    let back = Wire::new(vec![(Right, 2), (Left, 2)]);
+    let down = Wire::new(vec![(Up, 2), (Down, 2)]);
+    // each one retraces itself, but they only meet where they start.
+    assert!(super::crossings(&[back, down])
+      .iter()
+      .all(Crossing::is_self));
+  }
+
+  #[test]
+  fn many_wires() {
+    let wires = [
+      Wire::new(vec![(Right, 4)]),
+      Wire::new(vec![(Up, 2), (Right, 2), (Down, 4)]),
+      Wire::new(vec![(Down, 1), (Right, 2), (Up, 2)]),
+    ];
+
+    let crossings = crossings(&wires);
+    let at = |x, y| {
+      crossings
+        .iter()
+        .find(|c| c.point == Point::new(x, y))
+        .unwrap()
+        .clone()
+    };
+
+    assert_eq!(crossings.len(), 3);
+    assert_eq!(at(2, 0).wires, vec![(0, 2), (1, 6), (2, 4)]);
+    assert_eq!(at(2, 0).steps(), 12);
+    assert_eq!(at(2, -1).wires, vec![(1, 7), (2, 3)]);
+    assert_eq!(at(2, 1).wires, vec![(1, 5), (2, 5)]);
+  }
+
+  #[test]
+  fn self_crossings() {
+    // loops back through (2, 0), then turns around and retraces (2, -1).
+    let wire = Wire::new(vec![(Right, 4), (Up, 2), (Left, 2), (Down, 4), (Up, 1)]);
+    let other = Wire::new(vec![(Down, 1), (Right, 3)]);
+
+    let crossings = crossings(&[wire, other]);
+
+    assert_eq!(
+      crossings,
+      vec![
+        Crossing {
+          point: Point::new(2, -1),
+          distance: 3,
+          wires: vec![(0, 11), (1, 3)]
+        },
+        Crossing {
+          point: Point::new(2, 0),
+          distance: 2,
+          wires: vec![(0, 2)]
+        },
+      ]
+    );
+    assert!(crossings[1].is_self());
+  }
+}